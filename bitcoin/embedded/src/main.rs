@@ -12,6 +12,7 @@
 
 use alloc_cortex_m::CortexMHeap;
 // use panic_halt as _;
+use bitcoin::constants::genesis_block;
 use bitcoin::{Address, Network, PrivateKey};
 use bitcoin::secp256k1::ffi::types::AlignedType;
 use bitcoin::secp256k1::Secp256k1;
@@ -48,6 +49,16 @@ fn main() -> ! {
     hprintln!("Address: {}", address).unwrap();
 
     assert_eq!(address.to_string(), "bc1qpx9t9pzzl4qsydmhyt6ctrxxjd4ep549np9993".to_string());
+
+    // Build the genesis block, this only needs `alloc`.
+    let genesis = genesis_block(Network::Bitcoin);
+    hprintln!("Genesis: {}", genesis.block_hash()).unwrap();
+    assert!(genesis.check_merkle_root());
+    assert_eq!(
+        genesis.block_hash().to_string(),
+        "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f".to_string()
+    );
+
     // exit QEMU
     // NOTE do not run this on hardware; it can corrupt OpenOCD state
     debug::exit(debug::EXIT_SUCCESS);
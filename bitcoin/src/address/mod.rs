@@ -207,7 +207,7 @@ fn from_network(network: Network) -> Self {
 
         match network {
             Bitcoin => Self::Mainnet,
-            Testnet | Signet => Self::Testnets,
+            Testnet | Testnet4 | Signet => Self::Testnets,
             Regtest => Self::Regtest,
         }
     }
@@ -809,6 +809,10 @@ impl From<Address> for ScriptBuf {
     fn from(a: Address) -> Self { a.script_pubkey() }
 }
 
+impl From<&Address> for ScriptBuf {
+    fn from(a: &Address) -> Self { a.script_pubkey() }
+}
+
 // Alternate formatting `{:#}` is used to return uppercase version of bech32 addresses which should
 // be used in QR codes, see [`Address::to_qr_uri`].
 impl fmt::Display for Address {
@@ -849,7 +853,7 @@ fn from_str(s: &str) -> Result<Address<NetworkUnchecked>, ParseError> {
         }
         let data = base58::decode_check(s)?;
         if data.len() != 21 {
-            return Err(InvalidBase58PayloadLengthError { length: s.len() }.into());
+            return Err(InvalidBase58PayloadLengthError { length: data.len() }.into());
         }
 
         let (prefix, data) = data.split_first().expect("length checked above");
@@ -1040,6 +1044,70 @@ fn test_non_existent_segwit_version() {
         roundtrips(&addr, Bitcoin);
     }
 
+    #[test]
+    fn bip350_vectors() {
+        use crate::witness_version::Bech32Variant;
+
+        // Valid segwit addresses from BIP 350, along with their scriptPubkey.
+        let valid = [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                Bech32Variant::Bech32,
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                Bech32Variant::Bech32,
+            ),
+            (
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+                "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+                Bech32Variant::Bech32m,
+            ),
+            ("BC1SW50QGDZ25J", "6002751e", Bech32Variant::Bech32m),
+            (
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+                "5210751e76e8199196d454941c45d1b3a323",
+                Bech32Variant::Bech32m,
+            ),
+            (
+                "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+                "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+                Bech32Variant::Bech32,
+            ),
+            (
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+                Bech32Variant::Bech32m,
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                Bech32Variant::Bech32m,
+            ),
+        ];
+        for (s, spk, variant) in valid {
+            let addr = s.parse::<Address<_>>().unwrap().assume_checked();
+            assert_eq!(addr.script_pubkey(), ScriptBuf::from_hex(spk).unwrap());
+            assert_eq!(addr.witness_program().unwrap().version().encoding_variant(), variant);
+            // Encoding picks the checksum variant from the witness version.
+            assert_eq!(addr.to_string(), s.to_lowercase());
+        }
+
+        // Valid checksums but of the wrong variant for the witness version.
+        let invalid = [
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+            "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+            "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+            "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+        ];
+        for s in invalid {
+            assert!(s.parse::<Address<_>>().is_err(), "{} should not parse", s);
+        }
+    }
+
     #[test]
     fn test_address_debug() {
         // This is not really testing output of Debug but the ability and proper functioning
@@ -1348,6 +1416,65 @@ fn test_is_related_to_xonly_pubkey() {
         assert!(result);
     }
 
+    #[test]
+    fn address_from_script_per_network() {
+        use crate::address::script_pubkey::ScriptExt as _;
+        use crate::constants::genesis_block;
+        use crate::network::Network::{Regtest, Signet, Testnet4};
+
+        // (scriptPubkey, mainnet, testnet and signet, regtest)
+        let tests = [
+            (
+                "76a914162c5ea71c0b23f5b9022ef047c4a86470a5b07088ac",
+                "132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM",
+                "mhYCK8wSgwctbFnc3u9fCzzF8XgodR9KHX",
+                "mhYCK8wSgwctbFnc3u9fCzzF8XgodR9KHX",
+            ),
+            (
+                "a914162c5ea71c0b23f5b9022ef047c4a86470a5b07087",
+                "33iFwdLuRpW1uK1RTRqsoi8rR4NpDzk66k",
+                "2MuGU1NGw3H1N76dy8ZTkRf87dQaz5oKCau",
+                "2MuGU1NGw3H1N76dy8ZTkRf87dQaz5oKCau",
+            ),
+            (
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+                "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080",
+            ),
+            (
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                "bcrt1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qzf4jry",
+            ),
+            (
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47zagq",
+                "bcrt1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqc8gma6",
+            ),
+        ];
+        for (spk, main, test, reg) in tests {
+            let spk = ScriptBuf::from_hex(spk).unwrap();
+            for (network, want) in
+                [(Bitcoin, main), (Testnet, test), (Testnet4, test), (Signet, test), (Regtest, reg)]
+            {
+                let addr = Address::from_script(&spk, network).unwrap();
+                assert_eq!(addr.to_string(), want);
+                assert_eq!(addr.script_pubkey(), spk);
+            }
+        }
+
+        // The genesis output pays to a bare public key which has no address form.
+        let genesis_spk = &genesis_block(Bitcoin).txdata[0].output[0].script_pubkey;
+        assert!(genesis_spk.is_p2pk());
+        assert_eq!(
+            Address::from_script(genesis_spk, Bitcoin),
+            Err(FromScriptError::UnrecognizedScript)
+        );
+    }
+
     #[test]
     fn test_fail_address_from_script() {
         use crate::witness_program;
@@ -1369,6 +1496,82 @@ fn test_fail_address_from_script() {
         );
     }
 
+    #[test]
+    fn testnet4_addresses_match_testnet() {
+        use crate::network::Network::Testnet4;
+
+        let secp = Secp256k1::verification_only();
+        let key = "033bc8c83c52df5712229a2f72206d90192366c36428cb0c12b6af98324d97bfbc"
+            .parse::<CompressedPublicKey>()
+            .unwrap();
+        let script = ScriptBuf::new_p2wpkh(key.wpubkey_hash());
+        let internal_key = XOnlyPublicKey::from(key.0);
+
+        let build = |network: Network| {
+            [
+                Address::p2pkh(key, network),
+                Address::p2sh(&script, network).unwrap(),
+                Address::p2wpkh(key, network),
+                Address::p2tr(&secp, internal_key, None, network),
+            ]
+        };
+
+        for (addr, testnet) in build(Testnet4).iter().zip(build(Testnet).iter()) {
+            assert_eq!(addr, testnet);
+            assert!(addr.is_valid_for_network(Testnet));
+            assert!(addr.is_valid_for_network(Testnet4));
+            assert!(!addr.is_valid_for_network(Bitcoin));
+
+            let parsed = addr.to_string().parse::<Address<_>>().unwrap();
+            assert_eq!(parsed.require_network(Testnet4).unwrap(), *addr);
+            roundtrips(addr, Testnet4);
+        }
+    }
+
+    #[test]
+    fn parse_then_require_network() {
+        use crate::network::ALL_NETWORKS;
+
+        let key = "033bc8c83c52df5712229a2f72206d90192366c36428cb0c12b6af98324d97bfbc"
+            .parse::<CompressedPublicKey>()
+            .unwrap();
+
+        for network in ALL_NETWORKS {
+            for addr in [Address::p2pkh(key, network), Address::p2wpkh(key, network)] {
+                let unchecked = addr.to_string().parse::<Address<NetworkUnchecked>>().unwrap();
+                assert_eq!(unchecked.clone().require_network(network).unwrap(), addr);
+
+                for other in ALL_NETWORKS {
+                    // Base58 addresses only tell mainnet from the test networks, bech32 ones also
+                    // tell regtest apart.
+                    let valid = if addr.address_type() == Some(AddressType::P2pkh) {
+                        NetworkKind::from(other) == NetworkKind::from(network)
+                    } else {
+                        KnownHrp::from_network(other) == KnownHrp::from_network(network)
+                    };
+                    match unchecked.clone().require_network(other) {
+                        Ok(checked) if valid => assert_eq!(checked, addr),
+                        Err(ParseError::NetworkValidation(e)) if !valid => {
+                            assert_eq!(e.required, other);
+                            assert_eq!(e.address, unchecked);
+                        }
+                        res => panic!("unexpected result for {} on {}: {:?}", addr, other, res),
+                    }
+                }
+            }
+        }
+
+        let mut data = vec![PUBKEY_ADDRESS_PREFIX_MAIN];
+        data.extend_from_slice(key.pubkey_hash().as_ref());
+        data.push(0);
+        let err = ParseError::from(InvalidBase58PayloadLengthError { length: 22 });
+        assert_eq!(base58::encode_check(&data).parse::<Address<_>>(), Err(err));
+        data.pop();
+        data[0] = 0x30;
+        let err = ParseError::from(InvalidLegacyPrefixError { invalid: 0x30 });
+        assert_eq!(base58::encode_check(&data).parse::<Address<_>>(), Err(err));
+    }
+
     #[test]
     fn valid_address_parses_correctly() {
         let addr = "p2tr".parse::<AddressType>().expect("false negative while parsing address");
@@ -1407,4 +1610,37 @@ fn test_matches_script_pubkey() {
             }
         }
     }
+
+    #[test]
+    fn script_pubkey_bytes_per_address_type() {
+        let p2pkh = Address::p2pkh(PubkeyHash::from_byte_array([0x11; 20]), Bitcoin);
+        let p2sh = Address::p2sh_from_hash(ScriptHash::from_byte_array([0x22; 20]), Bitcoin);
+        let p2wpkh = Address::from_witness_program(
+            WitnessProgram::new(WitnessVersion::V0, &[0x33; 20]).unwrap(),
+            Bitcoin,
+        );
+        let p2wsh = Address::p2wsh_from_hash(WScriptHash::from_byte_array([0x44; 32]), Bitcoin);
+        let p2tr = Address::from_witness_program(
+            WitnessProgram::new(WitnessVersion::V1, &[0x55; 32]).unwrap(),
+            Bitcoin,
+        );
+
+        let cases = [
+            (
+                p2pkh,
+                AddressType::P2pkh,
+                [&[0x76, 0xa9, 0x14][..], &[0x11; 20], &[0x88, 0xac]].concat(),
+            ),
+            (p2sh, AddressType::P2sh, [&[0xa9, 0x14][..], &[0x22; 20], &[0x87]].concat()),
+            (p2wpkh, AddressType::P2wpkh, [&[0x00, 0x14][..], &[0x33; 20]].concat()),
+            (p2wsh, AddressType::P2wsh, [&[0x00, 0x20][..], &[0x44; 32]].concat()),
+            (p2tr, AddressType::P2tr, [&[0x51, 0x20][..], &[0x55; 32]].concat()),
+        ];
+        for (addr, address_type, spk) in cases {
+            assert_eq!(addr.address_type(), Some(address_type));
+            assert_eq!(addr.script_pubkey().as_bytes(), &spk[..]);
+            assert_eq!(ScriptBuf::from(&addr), addr.script_pubkey());
+            assert_eq!(Address::from_script(&addr.script_pubkey(), Bitcoin).unwrap(), addr);
+        }
+    }
 }
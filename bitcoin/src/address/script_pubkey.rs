@@ -10,11 +10,12 @@
     XOnlyPublicKey,
 };
 use crate::opcodes::all::*;
+use crate::prelude::Vec;
 use crate::script::witness_program::WitnessProgram;
 use crate::script::witness_version::WitnessVersion;
 use crate::script::{
-    self, Builder, PushBytes, RedeemScriptSizeError, Script, ScriptBuf, ScriptExt as _, ScriptHash,
-    WScriptHash, WitnessScriptSizeError,
+    self, Builder, Instruction, PushBytes, RedeemScriptSizeError, Script, ScriptBuf,
+    ScriptExt as _, ScriptHash, WScriptHash, WitnessScriptSizeError,
 };
 use crate::taproot::TapNodeHash;
 
@@ -92,6 +93,46 @@ fn is_p2pk(&self) -> bool { self.p2pk_pubkey_bytes().is_some() }
         fn p2pk_public_key(&self) -> Option<PublicKey> {
             PublicKey::from_slice(self.p2pk_pubkey_bytes()?).ok()
         }
+
+        /// Returns the threshold and public keys if this script is a bare multisig output.
+        ///
+        /// The script must be of the form `m <pubkey1> ... <pubkeyn> n OP_CHECKMULTISIG` with `n`
+        /// equal to the number of keys and `m` at most `n`, see [`is_multisig()`].
+        ///
+        /// Returns `None` if the script has any other form or if one of the keys is not a valid
+        /// public key.
+        ///
+        /// [`is_multisig()`]: crate::script::ScriptExt::is_multisig
+        fn parse_multisig(&self) -> Option<(usize, Vec<PublicKey>)> {
+            let mut instructions = self.instructions();
+
+            let threshold = match instructions.next()?.ok()? {
+                Instruction::Op(op) => op.decode_pushnum()?,
+                Instruction::PushBytes(_) => return None,
+            };
+
+            let mut keys = Vec::new();
+            let num_keys = loop {
+                match instructions.next()?.ok()? {
+                    Instruction::PushBytes(bytes) =>
+                        keys.push(PublicKey::from_slice(bytes.as_bytes()).ok()?),
+                    Instruction::Op(op) => break op.decode_pushnum()?,
+                }
+            };
+            if usize::from(num_keys) != keys.len() || threshold > num_keys {
+                return None;
+            }
+
+            match instructions.next()?.ok()? {
+                Instruction::Op(op) if op == OP_CHECKMULTISIG => {}
+                _ => return None,
+            }
+            if instructions.next().is_some() {
+                return None;
+            }
+
+            Some((usize::from(threshold), keys))
+        }
     }
 }
 
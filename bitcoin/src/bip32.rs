@@ -224,15 +224,21 @@ impl FromStr for ChildNumber {
     fn from_str(inp: &str) -> Result<ChildNumber, Error> {
         let is_hardened = inp.chars().last().map_or(false, |l| l == '\'' || l == 'h');
         Ok(if is_hardened {
-            ChildNumber::from_hardened_idx(
-                inp[0..inp.len() - 1].parse().map_err(|_| Error::InvalidChildNumberFormat)?,
-            )?
+            ChildNumber::from_hardened_idx(parse_index(&inp[0..inp.len() - 1])?)?
         } else {
-            ChildNumber::from_normal_idx(inp.parse().map_err(|_| Error::InvalidChildNumberFormat)?)?
+            ChildNumber::from_normal_idx(parse_index(inp)?)?
         })
     }
 }
 
+/// Parses a child index, only decimal digits are accepted (`u32::from_str` also allows a `+`).
+fn parse_index(s: &str) -> Result<u32, Error> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::InvalidChildNumberFormat);
+    }
+    s.parse().map_err(|_| Error::InvalidChildNumberFormat)
+}
+
 impl AsRef<[ChildNumber]> for ChildNumber {
     fn as_ref(&self) -> &[ChildNumber] { slice::from_ref(self) }
 }
@@ -984,6 +990,33 @@ fn test_parse_derivation_path() {
         assert_eq!(s.parse::<DerivationPath>(), s.to_string().into_derivation_path());
     }
 
+    #[test]
+    fn parse_derivation_path_rejects_malformed() {
+        assert_eq!(
+            "m/2147483648'".parse::<DerivationPath>(),
+            Err(Error::InvalidChildNumber(2147483648))
+        );
+        assert_eq!("m/4294967296".parse::<DerivationPath>(), Err(Error::InvalidChildNumberFormat));
+        assert_eq!("m/+1".parse::<DerivationPath>(), Err(Error::InvalidChildNumberFormat));
+        assert_eq!("m/0'/+1h".parse::<DerivationPath>(), Err(Error::InvalidChildNumberFormat));
+        assert_eq!("m/0'/".parse::<DerivationPath>(), Err(Error::InvalidChildNumberFormat));
+        assert_eq!("m/'".parse::<DerivationPath>(), Err(Error::InvalidChildNumberFormat));
+        assert_eq!("m/0''".parse::<DerivationPath>(), Err(Error::InvalidChildNumberFormat));
+    }
+
+    #[test]
+    fn parse_bip84_derivation_path() {
+        let want = DerivationPath::from(vec![
+            ChildNumber::from_hardened_idx(84).unwrap(),
+            ChildNumber::ZERO_HARDENED,
+            ChildNumber::ZERO_HARDENED,
+            ChildNumber::Normal { index: 0 },
+            ChildNumber::Normal { index: 0 },
+        ]);
+        assert_eq!("m/84'/0'/0'/0/0".parse::<DerivationPath>().unwrap(), want);
+        assert_eq!("m/84h/0h/0h/0/0".parse::<DerivationPath>().unwrap(), want);
+    }
+
     #[test]
     fn test_derivation_path_conversion_index() {
         let path = "0h/1/2'".parse::<DerivationPath>().unwrap();
@@ -1175,6 +1208,52 @@ fn test_vector_3() {
                   "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y");
     }
 
+    #[test]
+    fn network_version_bytes() {
+        use crate::network::{Network, ALL_NETWORKS};
+
+        let secp = Secp256k1::new();
+        let seed = hex!("000102030405060708090a0b0c0d0e0f");
+
+        for network in ALL_NETWORKS {
+            let xpriv = Xpriv::new_master(network, &seed).unwrap();
+            let xpub = Xpub::from_priv(&secp, &xpriv);
+            assert_eq!(xpriv.network, NetworkKind::from(network));
+
+            let (priv_prefix, pub_prefix) =
+                if network == Network::Bitcoin { ("xprv", "xpub") } else { ("tprv", "tpub") };
+            assert!(xpriv.to_string().starts_with(priv_prefix));
+            assert!(xpub.to_string().starts_with(pub_prefix));
+            assert_eq!(xpriv.to_string().parse::<Xpriv>().unwrap(), xpriv);
+            assert_eq!(xpub.to_string().parse::<Xpub>().unwrap(), xpub);
+        }
+
+        let mut data =
+            Xpub::from_priv(&secp, &Xpriv::new_master(Network::Bitcoin, &seed).unwrap()).encode();
+        data[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(Xpub::decode(&data), Err(Error::UnknownVersion([0xde, 0xad, 0xbe, 0xef])));
+    }
+
+    #[test]
+    fn ckd_pub_matches_derive_priv() {
+        let secp = Secp256k1::new();
+        let seed = hex!("000102030405060708090a0b0c0d0e0f");
+        let master = Xpriv::new_master(NetworkKind::Main, &seed).unwrap();
+        let master_pub = Xpub::from_priv(&secp, &master);
+        assert_eq!(master.fingerprint(&secp), master_pub.fingerprint());
+
+        let child = master.derive_priv(&secp, &[ChildNumber::ONE_NORMAL]);
+        let child_pub = master_pub.ckd_pub(&secp, ChildNumber::ONE_NORMAL).unwrap();
+        assert_eq!(Xpub::from_priv(&secp, &child), child_pub);
+        assert_eq!(child_pub.parent_fingerprint, master_pub.fingerprint());
+        assert_eq!(child_pub.depth, 1);
+
+        assert_eq!(
+            master_pub.ckd_pub(&secp, ChildNumber::ZERO_HARDENED),
+            Err(Error::CannotDeriveFromHardenedKey)
+        );
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     pub fn encode_decode_childnumber() {
@@ -8,20 +8,25 @@
 //! these blocks and the blockchain.
 
 use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
 use hashes::{sha256d, HashEngine};
 use io::{BufRead, Write};
 
 use super::Weight;
 use crate::consensus::{encode, Decodable, Encodable};
+use crate::constants::{INITIAL_SUBSIDY, SUBSIDY_HALVING_INTERVAL};
 use crate::internal_macros::{impl_consensus_encoding, impl_hashencode};
 use crate::merkle_tree::{MerkleNode as _, TxMerkleNode, WitnessMerkleNode};
 use crate::network::Params;
 use crate::pow::{CompactTarget, Target, Work};
 use crate::prelude::Vec;
 use crate::script::{self, ScriptExt as _};
-use crate::transaction::{Transaction, Wtxid};
-use crate::VarInt;
+#[cfg(feature = "std")]
+use crate::transaction::{FeeError, TxOut};
+use crate::transaction::{OutPoint, Transaction, Txid, Wtxid};
+use crate::{Amount, VarInt};
 
 hashes::hash_newtype! {
     /// A bitcoin block hash.
@@ -72,6 +77,9 @@ impl Header {
     // Serialized length of fields (version, prev_blockhash, merkle_root, time, bits, nonce)
     pub const SIZE: usize = 4 + 32 + 32 + 4 + 4 + 4; // 80
 
+    /// How far, in seconds, a header's time may be ahead of the local clock (two hours).
+    pub const MAX_FUTURE_BLOCK_TIME: u32 = 2 * 60 * 60;
+
     /// Returns the block hash.
     pub fn block_hash(&self) -> BlockHash {
         let mut engine = sha256d::Hash::engine();
@@ -111,6 +119,33 @@ pub fn validate_pow(&self, required_target: Target) -> Result<BlockHash, Validat
 
     /// Returns the total work of the block.
     pub fn work(&self) -> Work { self.target().to_work() }
+
+    /// Returns true if this is a genesis header, i.e. its `prev_blockhash` is all zeros.
+    ///
+    /// A genesis header has no predecessor, whether to skip checks that look at previous headers
+    /// (e.g. [`Header::check_timestamp`]) is up to the caller, they are not skipped here.
+    pub fn is_genesis(&self) -> bool { self.prev_blockhash == BlockHash::all_zeros() }
+
+    /// Checks the header's `time` against the median time past of the previous blocks and the
+    /// current time `now`.
+    ///
+    /// The time must be strictly greater than `median_time_past` (a consensus rule) and no more
+    /// than [`Header::MAX_FUTURE_BLOCK_TIME`] seconds ahead of `now` (a relay rule, the header may
+    /// become valid later).
+    ///
+    /// Both checks apply to every header, a genesis header is not exempted because anyone can put
+    /// an all-zeros `prev_blockhash` in a header. Callers that validate a chain from its genesis
+    /// block should skip it themselves, see [`Header::is_genesis`].
+    pub fn check_timestamp(&self, median_time_past: u32, now: u32) -> Result<(), TimeError> {
+        if self.time <= median_time_past {
+            return Err(TimeError::TooOld { time: self.time, median_time_past });
+        }
+        let max = now.saturating_add(Self::MAX_FUTURE_BLOCK_TIME);
+        if self.time > max {
+            return Err(TimeError::TooNew { time: self.time, max });
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Debug for Header {
@@ -157,10 +192,13 @@ impl Version {
     /// BIP-9 soft fork signal bits mask.
     const VERSION_BITS_MASK: u32 = 0x1FFF_FFFF;
 
+    /// Mask of the top three bits of a BIP-9 version, see [`Version::USE_VERSION_BITS`].
+    pub const VERSION_BITS_TOP_MASK: u32 = 0xE000_0000;
+
     /// 32bit value starting with `001` to use version bits.
     ///
     /// The value has the top three bits `001` which enables the use of version bits to signal for soft forks.
-    const USE_VERSION_BITS: u32 = 0x2000_0000;
+    pub const USE_VERSION_BITS: u32 = 0x2000_0000;
 
     /// Creates a [`Version`] from a signed 32 bit integer value.
     ///
@@ -173,6 +211,12 @@ pub const fn from_consensus(v: i32) -> Self { Version(v) }
     /// This is the data type used in consensus code in Bitcoin Core.
     pub fn to_consensus(self) -> i32 { self.0 }
 
+    /// Returns `true` if the top three bits of this version are `001`, i.e. it uses BIP-9 version
+    /// bits to signal for soft forks.
+    pub fn uses_version_bits(&self) -> bool {
+        (self.0 as u32) & Self::VERSION_BITS_TOP_MASK == Self::USE_VERSION_BITS
+    }
+
     /// Checks whether the version number is signalling a soft fork at the given bit.
     ///
     /// A block is signalling for a soft fork under BIP-9 if the first 3 bits are `001` and
@@ -184,13 +228,24 @@ pub fn is_signalling_soft_fork(&self, bit: u8) -> bool {
         }
 
         // To signal using version bits, the first three bits must be `001`.
-        if (self.0 as u32) & !Self::VERSION_BITS_MASK != Self::USE_VERSION_BITS {
+        if !self.uses_version_bits() {
             return false;
         }
 
         // The bit is set if signalling a soft fork.
         (self.0 as u32 & Self::VERSION_BITS_MASK) & (1 << bit) > 0
     }
+
+    /// Returns this version with the soft fork signal at the given bit set.
+    ///
+    /// Returns `None` if `bit` is not a signalling bit (only bits 0 through 28 are) or if this
+    /// version does not use version bits, see [`Version::uses_version_bits`].
+    pub fn with_soft_fork_signal(self, bit: u8) -> Option<Self> {
+        if bit > 28 || !self.uses_version_bits() {
+            return None;
+        }
+        Some(Version((self.0 as u32 | 1 << bit) as i32))
+    }
 }
 
 impl Default for Version {
@@ -217,6 +272,10 @@ fn consensus_decode<R: BufRead + ?Sized>(r: &mut R) -> Result<Self, encode::Erro
 ///
 /// [wiki-block]: https://en.bitcoin.it/wiki/Block
 ///
+/// Decoding with [`Decodable::consensus_decode`] consumes exactly one block from the reader, so
+/// consecutive blocks can be read one at a time from a stream, e.g. from a `blk*.dat` file
+/// wrapped in a `std::io::BufReader` and [`io::FromStd`], without loading the whole file.
+///
 /// ### Bitcoin Core References
 ///
 /// * [CBlock definition](https://github.com/bitcoin/bitcoin/blob/345457b542b6a980ccfbc868af0970a6f91d1b82/src/primitives/block.h#L62)
@@ -232,6 +291,26 @@ pub struct Block {
 impl_consensus_encoding!(Block, header, txdata);
 
 impl Block {
+    /// Creates a block from a header and its transactions.
+    ///
+    /// No checks are done, see [`Block::new_checked`] to also check that the two belong together.
+    pub fn new(header: Header, txdata: Vec<Transaction>) -> Block { Block { header, txdata } }
+
+    /// Creates a block from a header and its transactions, checking the header commits to them.
+    ///
+    /// # Errors
+    ///
+    /// If the Merkle root computed from `txdata` does not match the one in `header`, or if
+    /// `txdata` is empty.
+    pub fn new_checked(header: Header, txdata: Vec<Transaction>) -> Result<Block, ValidationError> {
+        let block = Block::new(header, txdata);
+        if block.check_merkle_root() {
+            Ok(block)
+        } else {
+            Err(ValidationError::BadMerkleRoot)
+        }
+    }
+
     /// Returns the block hash.
     pub fn block_hash(&self) -> BlockHash { self.header.block_hash() }
 
@@ -283,10 +362,46 @@ pub fn check_witness_commitment(&self) -> bool {
         false
     }
 
+    /// Returns an iterator over the txids of the transactions in this block.
+    ///
+    /// The txids are computed lazily as the iterator is advanced.
+    pub fn txids(&self) -> impl Iterator<Item = Txid> + '_ {
+        self.txdata.iter().map(|tx| tx.compute_txid())
+    }
+
+    /// Returns the index of the transaction with `txid` in this block, if it is included.
+    ///
+    /// Txids are computed until a match is found, if many transactions are looked up in the same
+    /// block it is cheaper to collect [`Block::txids`] once.
+    pub fn position_of(&self, txid: Txid) -> Option<usize> {
+        self.txids().position(|id| id == txid)
+    }
+
+    /// Returns true if the transaction with `txid` is included in this block.
+    ///
+    /// See [`Block::position_of`] for the cost.
+    pub fn contains_txid(&self, txid: Txid) -> bool { self.position_of(txid).is_some() }
+
+    /// Returns an iterator over the wtxids of the transactions in this block.
+    ///
+    /// The wtxids are computed lazily as the iterator is advanced. As in the witness Merkle tree
+    /// (see [BIP-141]) the wtxid of the coinbase transaction is yielded as all zeros.
+    ///
+    /// [BIP-141]: <https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#commitment-structure>
+    pub fn wtxids(&self) -> impl Iterator<Item = Wtxid> + '_ {
+        self.txdata.iter().enumerate().map(|(i, tx)| {
+            if i == 0 {
+                // Replace the first hash with zeroes.
+                Wtxid::all_zeros()
+            } else {
+                tx.compute_wtxid()
+            }
+        })
+    }
+
     /// Computes the transaction Merkle root.
     pub fn compute_merkle_root(&self) -> Option<TxMerkleNode> {
-        let hashes = self.txdata.iter().map(|obj| obj.compute_txid());
-        TxMerkleNode::calculate_root(hashes)
+        TxMerkleNode::calculate_root(self.txids())
     }
 
     /// Computes the witness commitment for the block's transaction list.
@@ -302,15 +417,7 @@ pub fn compute_witness_commitment(
 
     /// Computes the Merkle root of transactions hashed for witness.
     pub fn witness_root(&self) -> Option<WitnessMerkleNode> {
-        let hashes = self.txdata.iter().enumerate().map(|(i, t)| {
-            if i == 0 {
-                // Replace the first hash with zeroes.
-                Wtxid::all_zeros()
-            } else {
-                t.compute_wtxid()
-            }
-        });
-        WitnessMerkleNode::calculate_root(hashes)
+        WitnessMerkleNode::calculate_root(self.wtxids())
     }
 
     /// Returns the weight of the block.
@@ -351,6 +458,33 @@ pub fn total_size(&self) -> usize {
     /// Returns the coinbase transaction, if one is present.
     pub fn coinbase(&self) -> Option<&Transaction> { self.txdata.first() }
 
+    /// Computes the total fees paid by the non-coinbase transactions in this block.
+    ///
+    /// The fee of each transaction is the sum of the values of the outputs it spends, looked up
+    /// in `prevouts`, minus the sum of the values of its own outputs. The coinbase transaction
+    /// (the first transaction in the block) is skipped.
+    ///
+    /// # Errors
+    ///
+    /// If a spent output is missing from `prevouts`, if a transaction spends less than it creates,
+    /// or if any of the sums overflow.
+    #[cfg(feature = "std")]
+    pub fn total_fees(
+        &self,
+        prevouts: &HashMap<OutPoint, TxOut>,
+    ) -> Result<Amount, TotalFeesError> {
+        let mut total = Amount::ZERO;
+        for tx in self.txdata.iter().skip(1) {
+            let fee = tx.fee(prevouts).map_err(|e| match e {
+                FeeError::MissingPrevout(op) => TotalFeesError::MissingPrevout(op),
+                FeeError::NegativeFee => TotalFeesError::NegativeFee(tx.compute_txid()),
+                FeeError::Overflow => TotalFeesError::Overflow,
+            })?;
+            total = total.checked_add(fee).ok_or(TotalFeesError::Overflow)?;
+        }
+        Ok(total)
+    }
+
     /// Returns the block height, as encoded in the coinbase transaction according to BIP34.
     pub fn bip34_block_height(&self) -> Result<u64, Bip34Error> {
         // Citing the spec:
@@ -384,6 +518,71 @@ pub fn bip34_block_height(&self) -> Result<u64, Bip34Error> {
             _ => Err(Bip34Error::NotPresent),
         }
     }
+
+    /// Checks that the coinbase commits to the `expected` block height as required by BIP34.
+    ///
+    /// Blocks below the network's BIP34 activation height (see [`Params::bip34_height`]) are
+    /// exempt and always pass. Above it the block must have version 2 or greater and its coinbase
+    /// must push exactly `expected`.
+    pub fn check_coinbase_height(
+        &self,
+        expected: u64,
+        params: impl AsRef<Params>,
+    ) -> Result<(), Bip34Error> {
+        if expected < u64::from(params.as_ref().bip34_height.to_u32()) {
+            return Ok(());
+        }
+
+        let got = self.bip34_block_height()?;
+        if got == expected {
+            Ok(())
+        } else {
+            Err(Bip34Error::HeightMismatch { expected, got })
+        }
+    }
+
+    /// Returns the total value of all outputs of all transactions in the block.
+    ///
+    /// Returns `None` if the sum overflows, which a valid block never does.
+    pub fn output_value(&self) -> Option<Amount> {
+        TxOut::total_value(self.txdata.iter().flat_map(|tx| &tx.output))
+    }
+
+    /// Returns the total value of the outputs of the coinbase transaction.
+    ///
+    /// This is the amount claimed by the miner, the subsidy plus any fees they collected.
+    /// Returns `None` if the block has no transactions or if the sum overflows.
+    pub fn coinbase_value(&self) -> Option<Amount> { self.coinbase()?.output_value() }
+
+    /// Checks that the coinbase does not claim more than the block is allowed to pay out.
+    ///
+    /// The outputs of the coinbase transaction may sum to at most the [`block_subsidy`] at
+    /// `height` plus `total_fees`, the fees paid by the other transactions in the block (see
+    /// [`Block::total_fees`]). Claiming less is allowed, the difference is destroyed.
+    pub fn check_subsidy(&self, height: u32, total_fees: Amount) -> Result<(), SubsidyError> {
+        let coinbase = self.coinbase().ok_or(SubsidyError::NoCoinbase)?;
+        let max = block_subsidy(height).checked_add(total_fees).ok_or(SubsidyError::Overflow)?;
+        let claimed = coinbase.output_value().ok_or(SubsidyError::Overflow)?;
+
+        if claimed > max {
+            Err(SubsidyError::TooHigh { claimed, max })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Returns the new coins a block at `height` may create.
+///
+/// The subsidy starts at [`INITIAL_SUBSIDY`] and halves every [`SUBSIDY_HALVING_INTERVAL`] blocks
+/// until it reaches zero, so it never exceeds [`Amount::MAX_MONEY`] and no height can make it
+/// overflow. Note that regtest halves every 150 blocks instead, this function does not cover
+/// that.
+pub fn block_subsidy(height: u32) -> Amount {
+    let halvings = height / SUBSIDY_HALVING_INTERVAL;
+    // The shift is undefined past 63, the subsidy is long zero by then anyway.
+    let sats = INITIAL_SUBSIDY.to_sat().checked_shr(halvings).unwrap_or(0);
+    Amount::from_sat(sats)
 }
 
 impl From<Header> for BlockHash {
@@ -414,6 +613,13 @@ pub enum Bip34Error {
     UnexpectedPush(Vec<u8>),
     /// The BIP34 push was negative.
     NegativeHeight,
+    /// The BIP34 height differs from the height the block is being connected at.
+    HeightMismatch {
+        /// The height the block was expected to encode.
+        expected: u64,
+        /// The height encoded in the coinbase.
+        got: u64,
+    },
 }
 
 internals::impl_from_infallible!(Bip34Error);
@@ -429,6 +635,8 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                 write!(f, "unexpected byte push of > 8 bytes: {:?}", p)
             }
             NegativeHeight => write!(f, "negative BIP34 height"),
+            HeightMismatch { expected, got } =>
+                write!(f, "BIP34 height mismatch: expected {} got {}", expected, got),
         }
     }
 }
@@ -439,7 +647,91 @@ fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         use Bip34Error::*;
 
         match *self {
-            Unsupported | NotPresent | UnexpectedPush(_) | NegativeHeight => None,
+            Unsupported
+            | NotPresent
+            | UnexpectedPush(_)
+            | NegativeHeight
+            | HeightMismatch { .. } => None,
+        }
+    }
+}
+
+/// An error computing the total fees of a block, see [`Block::total_fees`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TotalFeesError {
+    /// The output spent by a transaction input was not provided.
+    MissingPrevout(OutPoint),
+    /// The transaction creates more value than it spends.
+    NegativeFee(Txid),
+    /// An amount sum overflowed.
+    Overflow,
+}
+
+internals::impl_from_infallible!(TotalFeesError);
+
+impl fmt::Display for TotalFeesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TotalFeesError::*;
+
+        match *self {
+            MissingPrevout(ref op) => write!(f, "missing spent output {}", op),
+            NegativeFee(ref txid) => write!(f, "transaction {} spends less than it creates", txid),
+            Overflow => f.write_str("amount overflow computing block fees"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TotalFeesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use TotalFeesError::*;
+
+        match *self {
+            MissingPrevout(_) | NegativeFee(_) | Overflow => None,
+        }
+    }
+}
+
+/// An error checking the coinbase payout of a block, see [`Block::check_subsidy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SubsidyError {
+    /// The block has no transactions.
+    NoCoinbase,
+    /// The coinbase claims more than the subsidy plus fees.
+    TooHigh {
+        /// The sum of the coinbase outputs.
+        claimed: Amount,
+        /// The subsidy plus fees.
+        max: Amount,
+    },
+    /// An amount sum overflowed.
+    Overflow,
+}
+
+internals::impl_from_infallible!(SubsidyError);
+
+impl fmt::Display for SubsidyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use SubsidyError::*;
+
+        match *self {
+            NoCoinbase => f.write_str("block has no coinbase transaction"),
+            TooHigh { claimed, max } =>
+                write!(f, "coinbase claims {} but at most {} is allowed", claimed, max),
+            Overflow => f.write_str("amount overflow checking block subsidy"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SubsidyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use SubsidyError::*;
+
+        match *self {
+            NoCoinbase | TooHigh { .. } | Overflow => None,
         }
     }
 }
@@ -452,6 +744,8 @@ pub enum ValidationError {
     BadProofOfWork,
     /// The `target` field of a block header did not match the expected difficulty.
     BadTarget,
+    /// The Merkle root of the header does not commit to the block's transactions.
+    BadMerkleRoot,
 }
 
 internals::impl_from_infallible!(ValidationError);
@@ -463,6 +757,7 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             BadProofOfWork => f.write_str("block target correct but not attained"),
             BadTarget => f.write_str("block target incorrect"),
+            BadMerkleRoot => f.write_str("block merkle root does not match its transactions"),
         }
     }
 }
@@ -473,7 +768,56 @@ fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         use self::ValidationError::*;
 
         match *self {
-            BadProofOfWork | BadTarget => None,
+            BadProofOfWork | BadTarget | BadMerkleRoot => None,
+        }
+    }
+}
+
+/// An error validating a block header's timestamp, see [`Header::check_timestamp`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimeError {
+    /// The time is not after the median time past of the previous blocks.
+    TooOld {
+        /// The header's time.
+        time: u32,
+        /// The median time past it must exceed.
+        median_time_past: u32,
+    },
+    /// The time is too far in the future.
+    TooNew {
+        /// The header's time.
+        time: u32,
+        /// The latest time that would have been accepted.
+        max: u32,
+    },
+}
+
+internals::impl_from_infallible!(TimeError);
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TimeError::*;
+
+        match *self {
+            TooOld { time, median_time_past } => write!(
+                f,
+                "block time {} is not after the median time past {}",
+                time, median_time_past
+            ),
+            TooNew { time, max } =>
+                write!(f, "block time {} is too far in the future (max {})", time, max),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use TimeError::*;
+
+        match *self {
+            TooOld { .. } | TooNew { .. } => None,
         }
     }
 }
@@ -485,6 +829,7 @@ mod tests {
 
     use super::*;
     use crate::consensus::encode::{deserialize, serialize};
+    use crate::network::ALL_NETWORKS;
     use crate::Network;
 
     #[test]
@@ -506,6 +851,100 @@ fn test_coinbase_and_bip34() {
         assert_eq!(bad.bip34_block_height(), Err(super::Bip34Error::UnexpectedPush(push)));
     }
 
+    #[test]
+    fn header_is_genesis() {
+        use crate::constants::genesis_block;
+
+        for network in ALL_NETWORKS {
+            let genesis = genesis_block(network).header;
+            assert!(genesis.is_genesis());
+
+            let next = Header { prev_blockhash: genesis.block_hash(), ..genesis };
+            assert!(!next.is_genesis());
+        }
+    }
+
+    #[test]
+    fn header_check_timestamp() {
+        use crate::constants::genesis_block;
+
+        let genesis = genesis_block(Network::Bitcoin).header;
+        // A zero `prev_blockhash` does not exempt a header from the checks.
+        assert_eq!(
+            genesis.check_timestamp(u32::MAX, u32::MAX),
+            Err(TimeError::TooOld { time: genesis.time, median_time_past: u32::MAX })
+        );
+        assert_eq!(
+            genesis.check_timestamp(0, 0),
+            Err(TimeError::TooNew { time: genesis.time, max: Header::MAX_FUTURE_BLOCK_TIME })
+        );
+
+        let header = Header { prev_blockhash: genesis.block_hash(), time: 1_000_000, ..genesis };
+        let limit = Header::MAX_FUTURE_BLOCK_TIME;
+
+        assert_eq!(header.check_timestamp(999_999, 1_000_000), Ok(()));
+        assert_eq!(
+            header.check_timestamp(1_000_000, 1_000_000),
+            Err(TimeError::TooOld { time: 1_000_000, median_time_past: 1_000_000 })
+        );
+        assert_eq!(header.check_timestamp(0, 1_000_000 - limit), Ok(()));
+        assert_eq!(
+            header.check_timestamp(0, 1_000_000 - limit - 1),
+            Err(TimeError::TooNew { time: 1_000_000, max: 999_999 })
+        );
+        // The clock saturates rather than overflowing.
+        assert_eq!(header.check_timestamp(0, u32::MAX), Ok(()));
+    }
+
+    #[test]
+    fn check_coinbase_height_at_activation() {
+        use crate::transaction::{TxIn, TxOut, Version as TxVersion};
+        use crate::{absolute, Amount, Sequence, Witness};
+
+        let params = Params::new(Network::Bitcoin);
+        let activation = u64::from(params.bip34_height.to_u32());
+        assert_eq!(activation, 227_931);
+
+        let block_at = |version: Version, height: u32| Block {
+            header: Header {
+                version,
+                prev_blockhash: BlockHash::all_zeros(),
+                merkle_root: TxMerkleNode::from_byte_array([0; 32]),
+                time: 0,
+                bits: CompactTarget::from_consensus(0x1d00ffff),
+                nonce: 0,
+            },
+            txdata: vec![Transaction {
+                version: TxVersion::ONE,
+                lock_time: absolute::LockTime::ZERO,
+                input: vec![TxIn {
+                    previous_output: OutPoint::NULL,
+                    script_sig: script::coinbase_script(height, b""),
+                    sequence: Sequence::MAX,
+                    witness: Witness::new(),
+                }],
+                output: vec![TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: script::ScriptBuf::new(),
+                }],
+            }],
+        };
+
+        let block = block_at(Version::TWO, 227_931);
+        assert_eq!(block.check_coinbase_height(activation, &params), Ok(()));
+        assert_eq!(
+            block.check_coinbase_height(activation + 1, &params),
+            Err(Bip34Error::HeightMismatch { expected: activation + 1, got: activation })
+        );
+        // Below activation the height is not checked at all.
+        assert_eq!(block.check_coinbase_height(activation - 1, &params), Ok(()));
+
+        let v1 = block_at(Version::ONE, 227_930);
+        assert_eq!(v1.check_coinbase_height(activation - 1, &params), Ok(()));
+        let v1 = block_at(Version::ONE, 227_931);
+        assert_eq!(v1.check_coinbase_height(activation, &params), Err(Bip34Error::Unsupported));
+    }
+
     #[test]
     fn block_test() {
         let params = Params::new(Network::Bitcoin);
@@ -551,6 +990,162 @@ fn block_test() {
         assert_eq!(serialize(&real_decode), some_block);
     }
 
+    #[test]
+    fn block_new_checked() {
+        use crate::constants::genesis_block;
+        use crate::locktime::absolute;
+
+        let block = genesis_block(Network::Bitcoin);
+        let (header, txdata) = (block.header, block.txdata.clone());
+
+        assert_eq!(Block::new(header, txdata.clone()), block);
+        assert_eq!(Block::new_checked(header, txdata.clone()), Ok(block));
+
+        // Transactions from another block.
+        let mut other = txdata;
+        other[0].lock_time = absolute::LockTime::from_consensus(1);
+        assert_eq!(Block::new_checked(header, other), Err(ValidationError::BadMerkleRoot));
+        assert_eq!(Block::new_checked(header, vec![]), Err(ValidationError::BadMerkleRoot));
+    }
+
+    #[test]
+    fn block_subsidy_halvings() {
+        assert_eq!(block_subsidy(0), Amount::from_int_btc(50));
+        assert_eq!(block_subsidy(209_999), Amount::from_int_btc(50));
+        assert_eq!(block_subsidy(210_000), Amount::from_int_btc(25));
+        assert_eq!(block_subsidy(840_000), Amount::from_sat(312_500_000));
+        // The 33rd halving is the first with a zero subsidy.
+        assert_eq!(block_subsidy(32 * 210_000), Amount::from_sat(1));
+        assert_eq!(block_subsidy(33 * 210_000), Amount::ZERO);
+        assert_eq!(block_subsidy(u32::MAX), Amount::ZERO);
+    }
+
+    #[test]
+    fn block_subsidy_total_supply() {
+        let mut total = Amount::ZERO;
+        for halving in 0..64 {
+            let subsidy = block_subsidy(halving * SUBSIDY_HALVING_INTERVAL);
+            assert!(subsidy <= INITIAL_SUBSIDY);
+            let era = subsidy.checked_mul(SUBSIDY_HALVING_INTERVAL.into()).unwrap();
+            total = total.checked_add(era).unwrap();
+        }
+        // Just under 21 million BTC, rounding down the subsidy loses a little.
+        assert_eq!(total, Amount::from_sat(2_099_999_997_690_000));
+        assert!(total <= Amount::MAX_MONEY);
+    }
+
+    #[test]
+    fn check_subsidy_mainnet_block_1() {
+        // Mainnet block 00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048
+        let block: Block = deserialize(&hex!("010000006fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000982051fd1e4ba744bbbe680e1fee14677ba1a3c3540bf7b1cdb606e857233e0e61bc6649ffff001d01e362990101000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0704ffff001d0104ffffffff0100f2052a0100000043410496b538e853519c726a2c91e61ec11600ae1390813a627c66fb8be7947be63c52da7589379515d4e0a604f8141781e62294721166bf621e73a82cbf2342c858eeac00000000")).unwrap();
+        assert_eq!(block.coinbase().unwrap().output[0].value, Amount::from_int_btc(50));
+
+        // No fees, the coinbase claims exactly the subsidy.
+        assert_eq!(block.check_subsidy(1, Amount::ZERO), Ok(()));
+        assert_eq!(block.check_subsidy(1, Amount::from_sat(1)), Ok(()));
+        // After the first halving the same payout is too much.
+        assert_eq!(
+            block.check_subsidy(210_000, Amount::ZERO),
+            Err(SubsidyError::TooHigh {
+                claimed: Amount::from_int_btc(50),
+                max: Amount::from_int_btc(25)
+            })
+        );
+        assert_eq!(block.check_subsidy(210_000, Amount::from_int_btc(25)), Ok(()));
+
+        let empty = Block { header: block.header, txdata: vec![] };
+        assert_eq!(empty.check_subsidy(1, Amount::ZERO), Err(SubsidyError::NoCoinbase));
+    }
+
+    #[test]
+    fn decode_consecutive_blocks_from_reader() {
+        use crate::constants::genesis_block;
+
+        let genesis = genesis_block(Network::Bitcoin);
+        let mut next = genesis.clone();
+        next.header.prev_blockhash = genesis.block_hash();
+
+        let mut stream = serialize(&genesis);
+        stream.extend(serialize(&next));
+        stream.extend([0xde, 0xad]);
+
+        let mut reader = &stream[..];
+        assert_eq!(Block::consensus_decode(&mut reader).unwrap(), genesis);
+        assert_eq!(reader.len(), stream.len() - genesis.total_size());
+        assert_eq!(Block::consensus_decode(&mut reader).unwrap(), next);
+        // The reader is left positioned right after the second block.
+        assert_eq!(reader, [0xde, 0xad]);
+        assert!(Block::consensus_decode(&mut reader).is_err());
+    }
+
+    #[test]
+    fn block_output_value() {
+        use crate::constants::genesis_block;
+
+        let block = genesis_block(Network::Bitcoin);
+        assert_eq!(block.output_value(), Some(Amount::from_int_btc(50)));
+
+        let mut tx = block.txdata[0].clone();
+        tx.output[0].value = Amount::from_sat(u64::MAX);
+        let overflow = Block { header: block.header, txdata: vec![tx, block.txdata[0].clone()] };
+        assert_eq!(overflow.output_value(), None);
+
+        let empty = Block { header: block.header, txdata: vec![] };
+        assert_eq!(empty.output_value(), Some(Amount::ZERO));
+    }
+
+    #[test]
+    fn block_coinbase_value() {
+        use crate::constants::genesis_block;
+
+        let block = genesis_block(Network::Bitcoin);
+        assert_eq!(block.coinbase_value(), Some(Amount::from_int_btc(50)));
+
+        // Outputs of other transactions are not counted.
+        let tx = block.txdata[0].clone();
+        let two = Block { header: block.header, txdata: vec![tx.clone(), tx] };
+        assert_eq!(two.coinbase_value(), Some(Amount::from_int_btc(50)));
+        assert_eq!(two.output_value(), Some(Amount::from_int_btc(100)));
+
+        let empty = Block { header: block.header, txdata: vec![] };
+        assert_eq!(empty.coinbase_value(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn block_total_fees() {
+        // Mainnet block 00000000b0c5a240b2a61d2e75692224efd4cbecdf6eaf4cc2cf477ca7c270e7
+        let block: Block = deserialize(&hex!("010000004ddccd549d28f385ab457e98d1b11ce80bfea2c5ab93015ade4973e400000000bf4473e53794beae34e64fccc471dace6ae544180816f89591894e0f417a914cd74d6e49ffff001d323b3a7b0201000000010000000000000000000000000000000000000000000000000000000000000000ffffffff0804ffff001d026e04ffffffff0100f2052a0100000043410446ef0102d1ec5240f0d061a4246c1bdef63fc3dbab7733052fbbf0ecd8f41fc26bf049ebb4f9527f374280259e7cfa99c48b0e3f39c51347a19a5819651503a5ac00000000010000000321f75f3139a013f50f315b23b0c9a2b6eac31e2bec98e5891c924664889942260000000049483045022100cb2c6b346a978ab8c61b18b5e9397755cbd17d6eb2fe0083ef32e067fa6c785a02206ce44e613f31d9a6b0517e46f3db1576e9812cc98d159bfdaf759a5014081b5c01ffffffff79cda0945903627c3da1f85fc95d0b8ee3e76ae0cfdc9a65d09744b1f8fc85430000000049483045022047957cdd957cfd0becd642f6b84d82f49b6cb4c51a91f49246908af7c3cfdf4a022100e96b46621f1bffcf5ea5982f88cef651e9354f5791602369bf5a82a6cd61a62501fffffffffe09f5fe3ffbf5ee97a54eb5e5069e9da6b4856ee86fc52938c2f979b0f38e82000000004847304402204165be9a4cbab8049e1af9723b96199bfd3e85f44c6b4c0177e3962686b26073022028f638da23fc003760861ad481ead4099312c60030d4cb57820ce4d33812a5ce01ffffffff01009d966b01000000434104ea1feff861b51fe3f5f8a3b12d0f4712db80e919548a80839fc47c6a21e66d957e9c5d8cd108c7a2d2324bad71f9904ac0ae7336507d785b17a2c115e427a32fac00000000")).unwrap();
+        let spending = &block.txdata[1];
+        // The second transaction creates a single 61 BTC output.
+        assert_eq!(spending.output[0].value, Amount::from_int_btc(61));
+
+        let mut prevouts = HashMap::new();
+        for input in &spending.input {
+            let prevout =
+                TxOut { value: Amount::from_int_btc(21), script_pubkey: script::ScriptBuf::new() };
+            prevouts.insert(input.previous_output, prevout);
+        }
+        assert_eq!(block.total_fees(&prevouts), Ok(Amount::from_int_btc(2)));
+
+        // Coinbase transactions have no fees and their inputs are never looked up.
+        let coinbase_only = Block { header: block.header, txdata: vec![block.txdata[0].clone()] };
+        assert_eq!(coinbase_only.total_fees(&HashMap::new()), Ok(Amount::ZERO));
+
+        let missing = spending.input[0].previous_output;
+        let mut partial = prevouts.clone();
+        partial.remove(&missing);
+        assert_eq!(block.total_fees(&partial), Err(TotalFeesError::MissingPrevout(missing)));
+
+        for prevout in prevouts.values_mut() {
+            prevout.value = Amount::from_int_btc(20);
+        }
+        assert_eq!(
+            block.total_fees(&prevouts),
+            Err(TotalFeesError::NegativeFee(spending.compute_txid()))
+        );
+    }
+
     // Check testnet block 000000000000045e0b1660b6445b5e5c5ab63c9a4f956be7e1e69be04fa4497b
     #[test]
     fn segwit_block_test() {
@@ -589,6 +1184,44 @@ fn segwit_block_test() {
         assert_eq!(serialize(&real_decode), segwit_block);
     }
 
+    #[test]
+    fn block_txids_and_wtxids() {
+        let segwit_block = include_bytes!("../../tests/data/testnet_block_000000000000045e0b1660b6445b5e5c5ab63c9a4f956be7e1e69be04fa4497b.raw").to_vec();
+        let block: Block = deserialize(&segwit_block).unwrap();
+
+        let txids = block.txids().collect::<Vec<_>>();
+        assert_eq!(txids.len(), block.txdata.len());
+        for (txid, tx) in txids.iter().zip(&block.txdata) {
+            assert_eq!(*txid, tx.compute_txid());
+        }
+
+        let wtxids = block.wtxids().collect::<Vec<_>>();
+        assert_eq!(wtxids.len(), block.txdata.len());
+        assert_eq!(wtxids[0], Wtxid::all_zeros());
+        assert_ne!(block.txdata[0].compute_wtxid(), Wtxid::all_zeros());
+        for (wtxid, tx) in wtxids.iter().zip(&block.txdata).skip(1) {
+            assert_eq!(*wtxid, tx.compute_wtxid());
+        }
+
+        // Only the first few identifiers are computed.
+        assert_eq!(block.txids().nth(1), Some(txids[1]));
+        assert!(block.check_merkle_root());
+        assert!(block.check_witness_commitment());
+    }
+
+    #[test]
+    fn block_position_of() {
+        let segwit_block = include_bytes!("../../tests/data/testnet_block_000000000000045e0b1660b6445b5e5c5ab63c9a4f956be7e1e69be04fa4497b.raw").to_vec();
+        let block: Block = deserialize(&segwit_block).unwrap();
+
+        for (i, txid) in block.txids().enumerate() {
+            assert_eq!(block.position_of(txid), Some(i));
+            assert!(block.contains_txid(txid));
+        }
+        assert_eq!(block.position_of(Txid::all_zeros()), None);
+        assert!(!block.contains_txid(Txid::all_zeros()));
+    }
+
     #[test]
     fn block_version_test() {
         let block = hex!("ffffff7f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
@@ -656,6 +1289,27 @@ fn soft_fork_signalling() {
         assert!(segwit_signal.is_signalling_soft_fork(1));
         assert!(!segwit_signal.is_signalling_soft_fork(2));
     }
+
+    #[test]
+    fn soft_fork_signal_setting() {
+        let version = Version::NO_SOFT_FORK_SIGNALLING;
+        assert!(version.uses_version_bits());
+        assert_eq!(version.to_consensus() as u32 & Version::VERSION_BITS_TOP_MASK, 0x2000_0000);
+
+        let taproot = version.with_soft_fork_signal(2).unwrap();
+        assert_eq!(taproot, Version::from_consensus(0x2000_0004));
+        assert!(taproot.is_signalling_soft_fork(2));
+        assert!(!taproot.is_signalling_soft_fork(1));
+
+        let both = taproot.with_soft_fork_signal(1).unwrap();
+        assert!(both.is_signalling_soft_fork(1) && both.is_signalling_soft_fork(2));
+
+        assert_eq!(version.with_soft_fork_signal(29), None);
+        // Pre BIP-9 versions can not signal.
+        assert!(!Version::TWO.uses_version_bits());
+        assert_eq!(Version::TWO.with_soft_fork_signal(0), None);
+        assert!(!Version::from_consensus(0x6000_0004).is_signalling_soft_fork(2));
+    }
 }
 
 #[cfg(bench)]
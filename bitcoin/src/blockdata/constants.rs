@@ -6,7 +6,6 @@
 //! consensus code. In particular, it defines the genesis block and its
 //! single transaction.
 
-use hashes::sha256d;
 use internals::impl_array_newtype;
 
 use crate::block::{self, Block};
@@ -15,6 +14,8 @@
 use crate::network::{Network, Params};
 use crate::opcodes::all::*;
 use crate::pow::CompactTarget;
+use crate::prelude::{String, ToString};
+use crate::script::{PushBytes, ScriptBuf};
 use crate::transaction::{self, OutPoint, Transaction, TxIn, TxOut};
 use crate::witness::Witness;
 use crate::{script, Amount, BlockHash, Sequence};
@@ -38,14 +39,22 @@
 pub const PUBKEY_ADDRESS_PREFIX_TEST: u8 = 111; // 0x6f
 /// Test (tesnet, signet, regtest) script address prefix.
 pub const SCRIPT_ADDRESS_PREFIX_TEST: u8 = 196; // 0xc4
+/// Mainnet (bitcoin) WIF private key prefix.
+pub const SECRET_KEY_PREFIX_MAIN: u8 = 128; // 0x80
+/// Test (tesnet, signet, regtest) WIF private key prefix.
+pub const SECRET_KEY_PREFIX_TEST: u8 = 239; // 0xef
 /// The maximum allowed redeem script size for a P2SH output.
 pub const MAX_REDEEM_SCRIPT_SIZE: usize = 520;
 /// The maximum allowed redeem script size of the witness script.
 pub const MAX_WITNESS_SCRIPT_SIZE: usize = 10_000;
 /// The maximum allowed size of any single witness stack element.
 pub const MAX_STACK_ELEMENT_SIZE: usize = 520;
+/// The maximum allowed size of a script, larger scripts always fail to execute.
+pub const MAX_SCRIPT_SIZE: usize = 10_000;
 /// How may blocks between halvings.
 pub const SUBSIDY_HALVING_INTERVAL: u32 = 210_000;
+/// The subsidy of a block before the first halving, 50 BTC.
+pub const INITIAL_SUBSIDY: Amount = Amount::from_int_btc(50);
 /// Maximum allowed value for an integer in Script.
 #[deprecated(
     since = "TBD",
@@ -72,8 +81,32 @@
     0x8a, 0x4c, 0x70, 0x2b, 0x6b, 0xf1, 0x1d, 0x5f
 ];
 
+// The message embedded in the coinbase of the Bitcoin genesis block, also used by most test networks.
+const BITCOIN_GENESIS_MESSAGE: &[u8] =
+    b"The Times 03/Jan/2009 Chancellor on brink of second bailout for banks";
+
+// The message embedded in the coinbase of the testnet4 genesis block.
+const TESTNET4_GENESIS_MESSAGE: &[u8] =
+    b"03/May/2024 000000000000000000001ebd58c244970b3aa9d783bb001011fbe8ea8e98e00e";
+
 /// Constructs and returns the coinbase (and only) transaction of the Bitcoin genesis block.
 fn bitcoin_genesis_tx() -> Transaction {
+    let out_script =
+        script::Builder::new().push_slice(GENESIS_OUTPUT_PK).push_opcode(OP_CHECKSIG).into_script();
+    genesis_tx(genesis_coinbase_script_sig(Network::Bitcoin), out_script)
+}
+
+/// Constructs and returns the coinbase (and only) transaction of the testnet4 genesis block.
+///
+/// The output pays to an all-zero public key, see BIP-94.
+fn testnet4_genesis_tx() -> Transaction {
+    let out_script =
+        script::Builder::new().push_slice([0u8; 33]).push_opcode(OP_CHECKSIG).into_script();
+    genesis_tx(genesis_coinbase_script_sig(Network::Testnet4), out_script)
+}
+
+/// Constructs a genesis coinbase transaction.
+fn genesis_tx(in_script: ScriptBuf, out_script: ScriptBuf) -> Transaction {
     // Base
     let mut ret = Transaction {
         version: transaction::Version::ONE,
@@ -83,22 +116,15 @@ fn bitcoin_genesis_tx() -> Transaction {
     };
 
     // Inputs
-    let in_script = script::Builder::new()
-        .push_int(486604799)
-        .push_int_non_minimal(4)
-        .push_slice(b"The Times 03/Jan/2009 Chancellor on brink of second bailout for banks")
-        .into_script();
     ret.input.push(TxIn {
-        previous_output: OutPoint::null(),
+        previous_output: OutPoint::NULL,
         script_sig: in_script,
         sequence: Sequence::MAX,
         witness: Witness::default(),
     });
 
     // Outputs
-    let out_script =
-        script::Builder::new().push_slice(GENESIS_OUTPUT_PK).push_opcode(OP_CHECKSIG).into_script();
-    ret.output.push(TxOut { value: Amount::from_sat(50 * 100_000_000), script_pubkey: out_script });
+    ret.output.push(TxOut { value: INITIAL_SUBSIDY, script_pubkey: out_script });
 
     // end
     ret
@@ -106,16 +132,19 @@ fn bitcoin_genesis_tx() -> Transaction {
 
 /// Constructs and returns the genesis block.
 pub fn genesis_block(params: impl AsRef<Params>) -> Block {
-    let txdata = vec![bitcoin_genesis_tx()];
-    let hash: sha256d::Hash = txdata[0].compute_txid().into();
-    let merkle_root = hash.into();
-    match params.as_ref().network {
+    let network = params.as_ref().network;
+    let txdata = match network {
+        Network::Testnet4 => vec![testnet4_genesis_tx()],
+        _ => vec![bitcoin_genesis_tx()],
+    };
+    let merkle_root = txdata[0].compute_txid().into();
+    match network {
         Network::Bitcoin => Block {
             header: block::Header {
                 version: block::Version::ONE,
                 prev_blockhash: BlockHash::all_zeros(),
                 merkle_root,
-                time: 1231006505,
+                time: network.genesis_block_time(),
                 bits: CompactTarget::from_consensus(0x1d00ffff),
                 nonce: 2083236893,
             },
@@ -126,18 +155,29 @@ pub fn genesis_block(params: impl AsRef<Params>) -> Block {
                 version: block::Version::ONE,
                 prev_blockhash: BlockHash::all_zeros(),
                 merkle_root,
-                time: 1296688602,
+                time: network.genesis_block_time(),
                 bits: CompactTarget::from_consensus(0x1d00ffff),
                 nonce: 414098458,
             },
             txdata,
         },
+        Network::Testnet4 => Block {
+            header: block::Header {
+                version: block::Version::ONE,
+                prev_blockhash: BlockHash::all_zeros(),
+                merkle_root,
+                time: network.genesis_block_time(),
+                bits: CompactTarget::from_consensus(0x1d00ffff),
+                nonce: 393743547,
+            },
+            txdata,
+        },
         Network::Signet => Block {
             header: block::Header {
                 version: block::Version::ONE,
                 prev_blockhash: BlockHash::all_zeros(),
                 merkle_root,
-                time: 1598918400,
+                time: network.genesis_block_time(),
                 bits: CompactTarget::from_consensus(0x1e0377ae),
                 nonce: 52613770,
             },
@@ -148,7 +188,7 @@ pub fn genesis_block(params: impl AsRef<Params>) -> Block {
                 version: block::Version::ONE,
                 prev_blockhash: BlockHash::all_zeros(),
                 merkle_root,
-                time: 1296688602,
+                time: network.genesis_block_time(),
                 bits: CompactTarget::from_consensus(0x207fffff),
                 nonce: 2,
             },
@@ -157,7 +197,57 @@ pub fn genesis_block(params: impl AsRef<Params>) -> Block {
     }
 }
 
+/// Returns the message embedded in the coinbase script sig of the genesis block.
+///
+/// This is the Times headline of 03/Jan/2009 for all networks except testnet4, whose message
+/// references a mainnet block from the day it was created.
+pub fn genesis_coinbase_message(params: impl AsRef<Params>) -> &'static [u8] {
+    match params.as_ref().network {
+        Network::Testnet4 => TESTNET4_GENESIS_MESSAGE,
+        _ => BITCOIN_GENESIS_MESSAGE,
+    }
+}
+
+/// Returns the script sig of the genesis coinbase input.
+///
+/// The script pushes the number 486604799 (`0x1d00ffff`, the genesis `bits`), then a 4 using a
+/// non-minimal one byte push and finally the [`genesis_coinbase_message`].
+pub fn genesis_coinbase_script_sig(params: impl AsRef<Params>) -> ScriptBuf {
+    let message = <&PushBytes>::try_from(genesis_coinbase_message(params))
+        .expect("the messages are below the push size limit");
+    script::Builder::new()
+        .push_int(486604799)
+        .push_int_non_minimal(4)
+        .push_slice(message)
+        .into_script()
+}
+
+/// Returns the network whose genesis block has the hash `hash`, if it is a known genesis block.
+///
+/// This compares against the precomputed [`ChainHash`] constants, so no genesis block is built.
+pub fn network_for_genesis_hash(hash: BlockHash) -> Option<Network> {
+    Network::from_chain_hash(ChainHash::from_genesis_block_hash(hash))
+}
+
 /// The uniquely identifying hash of the target blockchain.
+///
+/// # Byte order
+///
+/// A `ChainHash` holds the genesis block hash in internal byte order, which is what BOLT 0 and the
+/// P2P protocol use. Its `Display`, `LowerHex` and `FromStr` implementations work on the bytes in
+/// that order, so they do *not* match the hash shown by block explorers and `bitcoin-cli`, which
+/// reverse it. Use [`ChainHash::to_display_hex`] or [`ChainHash::to_genesis_block_hash`] to get
+/// the familiar form.
+///
+/// ```
+/// use bitcoin::constants::ChainHash;
+///
+/// let internal = "6fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000";
+/// let displayed = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+///
+/// assert_eq!(ChainHash::BITCOIN.to_string(), internal);
+/// assert_eq!(ChainHash::BITCOIN.to_display_hex(), displayed);
+/// ```
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ChainHash([u8; 32]);
 impl_array_newtype!(ChainHash, u8, 32);
@@ -175,6 +265,11 @@ impl ChainHash {
         67, 73, 127, 215, 248, 38, 149, 113, 8, 244, 163, 15, 217, 206, 195, 174, 186, 121, 151,
         32, 132, 233, 14, 173, 1, 234, 51, 9, 0, 0, 0, 0,
     ]);
+    /// `ChainHash` for testnet4 bitcoin.
+    pub const TESTNET4: Self = Self([
+        67, 240, 139, 218, 176, 80, 227, 91, 86, 124, 134, 75, 145, 244, 127, 80, 174, 114, 90,
+        226, 222, 83, 188, 251, 186, 242, 132, 218, 0, 0, 0, 0,
+    ]);
     /// `ChainHash` for signet bitcoin.
     pub const SIGNET: Self = Self([
         246, 30, 238, 59, 99, 163, 128, 164, 119, 160, 99, 175, 50, 178, 187, 201, 124, 159, 249,
@@ -192,7 +287,7 @@ impl ChainHash {
     /// for specification.
     pub fn using_genesis_block(params: impl AsRef<Params>) -> Self {
         let network = params.as_ref().network;
-        let hashes = [Self::BITCOIN, Self::TESTNET, Self::SIGNET, Self::REGTEST];
+        let hashes = [Self::BITCOIN, Self::TESTNET, Self::TESTNET4, Self::SIGNET, Self::REGTEST];
         hashes[network as usize]
     }
 
@@ -201,14 +296,32 @@ pub fn using_genesis_block(params: impl AsRef<Params>) -> Self {
     /// See [BOLT 0](https://github.com/lightning/bolts/blob/ffeece3dab1c52efdb9b53ae476539320fa44938/00-introduction.md#chain_hash)
     /// for specification.
     pub const fn using_genesis_block_const(network: Network) -> Self {
-        let hashes = [Self::BITCOIN, Self::TESTNET, Self::SIGNET, Self::REGTEST];
+        let hashes = [Self::BITCOIN, Self::TESTNET, Self::TESTNET4, Self::SIGNET, Self::REGTEST];
         hashes[network as usize]
     }
 
     /// Converts genesis block hash into `ChainHash`.
-    pub fn from_genesis_block_hash(block_hash: crate::BlockHash) -> Self {
+    ///
+    /// This is a `const fn` so the chain hash of a custom network can be computed at compile time.
+    pub const fn from_genesis_block_hash(block_hash: crate::BlockHash) -> Self {
         ChainHash(block_hash.to_byte_array())
     }
+
+    /// Converts this `ChainHash` back into the genesis block hash it was created from.
+    pub const fn to_genesis_block_hash(self) -> BlockHash { BlockHash::from_byte_array(self.0) }
+
+    /// Returns true if `block_hash` is the genesis block hash this `ChainHash` was created from.
+    ///
+    /// Both types store the hash in internal byte order so no reversal is involved.
+    pub fn matches_block_hash(&self, block_hash: BlockHash) -> bool {
+        self.0 == block_hash.to_byte_array()
+    }
+
+    /// Returns the genesis block hash as hex in the reversed (displayed) byte order.
+    ///
+    /// This is the form shown by block explorers and `bitcoin-cli getblockhash 0`. The `Display`
+    /// implementation of `ChainHash` instead encodes the bytes in internal order.
+    pub fn to_display_hex(&self) -> String { self.to_genesis_block_hash().to_string() }
 }
 
 #[cfg(test)]
@@ -217,7 +330,7 @@ mod test {
 
     use super::*;
     use crate::consensus::encode::serialize;
-    use crate::network::params;
+    use crate::network::{params, ALL_NETWORKS};
     use crate::Txid;
 
     #[test]
@@ -244,6 +357,35 @@ fn bitcoin_genesis_first_transaction() {
         );
     }
 
+    #[test]
+    fn address_prefixes() {
+        use crate::{Address, PubkeyHash, ScriptHash};
+
+        const HASH: [u8; 20] = hex_lit::hex!("162c5ea71c0b23f5b9022ef047c4a86470a5b070");
+
+        let encode = |prefix: u8| {
+            let mut data = [prefix; 21];
+            data[1..].copy_from_slice(&HASH);
+            base58::encode_check(&data)
+        };
+        assert_eq!(encode(PUBKEY_ADDRESS_PREFIX_MAIN), "132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM");
+        assert_eq!(encode(SCRIPT_ADDRESS_PREFIX_MAIN), "33iFwdLuRpW1uK1RTRqsoi8rR4NpDzk66k");
+        assert_eq!(encode(PUBKEY_ADDRESS_PREFIX_TEST), "mhYCK8wSgwctbFnc3u9fCzzF8XgodR9KHX");
+        assert_eq!(encode(SCRIPT_ADDRESS_PREFIX_TEST), "2MuGU1NGw3H1N76dy8ZTkRf87dQaz5oKCau");
+
+        // All test networks share the same prefixes.
+        for network in ALL_NETWORKS {
+            let (pubkey_prefix, script_prefix) = match network {
+                Network::Bitcoin => (PUBKEY_ADDRESS_PREFIX_MAIN, SCRIPT_ADDRESS_PREFIX_MAIN),
+                _ => (PUBKEY_ADDRESS_PREFIX_TEST, SCRIPT_ADDRESS_PREFIX_TEST),
+            };
+            let p2pkh = Address::p2pkh(PubkeyHash::from_byte_array(HASH), network);
+            assert_eq!(p2pkh.to_string(), encode(pubkey_prefix));
+            let p2sh = Address::p2sh_from_hash(ScriptHash::from_byte_array(HASH), network);
+            assert_eq!(p2sh.to_string(), encode(script_prefix));
+        }
+    }
+
     #[test]
     fn bitcoin_genesis_block_calling_convention() {
         // This is the best.
@@ -294,6 +436,24 @@ fn testnet_genesis_full_block() {
         );
     }
 
+    #[test]
+    fn testnet4_genesis_full_block() {
+        let gen = genesis_block(&params::TESTNET4);
+        assert_eq!(gen.header.version, block::Version::ONE);
+        assert_eq!(gen.header.prev_blockhash, BlockHash::all_zeros());
+        assert_eq!(
+            gen.header.merkle_root.to_string(),
+            "7aa0a7ae1e223414cb807e40cd57e667b718e42aaf9306db9102fe28912b7b4e"
+        );
+        assert_eq!(gen.header.time, 1714777860);
+        assert_eq!(gen.header.bits, CompactTarget::from_consensus(0x1d00ffff));
+        assert_eq!(gen.header.nonce, 393743547);
+        assert_eq!(
+            gen.header.block_hash().to_string(),
+            "00000000da84f2bafbbc53dee25a72ae507ff4914b867c565be350b0da8bf043"
+        );
+    }
+
     #[test]
     fn signet_genesis_full_block() {
         let gen = genesis_block(&params::SIGNET);
@@ -333,6 +493,7 @@ fn chain_hash_and_genesis_block(network: Network) {
         match network {
             Network::Bitcoin => {},
             Network::Testnet => {},
+            Network::Testnet4 => {},
             Network::Signet => {},
             Network::Regtest => {},
             _ => panic!("update ChainHash::using_genesis_block and chain_hash_genesis_block with new variants"),
@@ -353,6 +514,7 @@ fn $test_name() {
     chain_hash_genesis_block! {
         mainnet_chain_hash_genesis_block, Network::Bitcoin;
         testnet_chain_hash_genesis_block, Network::Testnet;
+        testnet4_chain_hash_genesis_block, Network::Testnet4;
         signet_chain_hash_genesis_block, Network::Signet;
         regtest_chain_hash_genesis_block, Network::Regtest;
     }
@@ -364,4 +526,115 @@ fn mainnet_chain_hash_test_vector() {
         let want = "6fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000";
         assert_eq!(got, want);
     }
+
+    #[test]
+    fn chain_hash_byte_order() {
+        for network in ALL_NETWORKS {
+            let chain_hash = ChainHash::using_genesis_block_const(network);
+            let genesis_hash = genesis_block(network).block_hash();
+
+            assert_eq!(chain_hash.to_genesis_block_hash(), genesis_hash);
+            assert_eq!(chain_hash.to_display_hex(), genesis_hash.to_string());
+            assert_ne!(chain_hash.to_string(), genesis_hash.to_string());
+        }
+        assert_eq!(
+            ChainHash::REGTEST.to_display_hex(),
+            "0f9188f13cb7b2c71f2a335e3a4fc328bf5beb436012afca590b1a11466e2206"
+        );
+    }
+
+    #[test]
+    fn chain_hash_matches_block_hash() {
+        let mainnet = genesis_block(Network::Bitcoin).block_hash();
+        let testnet = genesis_block(Network::Testnet).block_hash();
+
+        assert!(ChainHash::BITCOIN.matches_block_hash(mainnet));
+        assert!(!ChainHash::BITCOIN.matches_block_hash(testnet));
+        assert!(ChainHash::TESTNET.matches_block_hash(testnet));
+
+        // The displayed (reversed) form of the hash is not a match.
+        let mut reversed = mainnet.to_byte_array();
+        reversed.reverse();
+        assert!(!ChainHash::BITCOIN.matches_block_hash(BlockHash::from_byte_array(reversed)));
+    }
+
+    #[test]
+    fn chain_hash_from_genesis_block_hash_const() {
+        const GENESIS: BlockHash = BlockHash::from_byte_array([
+            111, 226, 140, 10, 182, 241, 179, 114, 193, 166, 162, 70, 174, 99, 247, 79, 147, 30,
+            131, 101, 225, 90, 8, 156, 104, 214, 25, 0, 0, 0, 0, 0,
+        ]);
+        const CHAIN: ChainHash = ChainHash::from_genesis_block_hash(GENESIS);
+
+        assert_eq!(CHAIN, ChainHash::BITCOIN);
+        assert_eq!(GENESIS, genesis_block(Network::Bitcoin).block_hash());
+    }
+
+    #[test]
+    fn genesis_coinbase_messages() {
+        use crate::script::ScriptExt as _;
+
+        for network in ALL_NETWORKS {
+            let block = genesis_block(network);
+            let script_sig = &block.txdata[0].input[0].script_sig;
+            assert_eq!(script_sig.last_push(), Some(genesis_coinbase_message(network)));
+        }
+        assert!(genesis_coinbase_message(Network::Bitcoin).starts_with(b"The Times 03/Jan/2009"));
+        assert!(genesis_coinbase_message(&params::TESTNET4).starts_with(b"03/May/2024"));
+    }
+
+    #[test]
+    fn genesis_coinbase_script_sigs() {
+        let script_sig = genesis_coinbase_script_sig(Network::Bitcoin);
+        assert_eq!(
+            script_sig.as_bytes(),
+            hex!("04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73")
+        );
+        assert_eq!(script_sig, genesis_block(Network::Bitcoin).txdata[0].input[0].script_sig);
+
+        let script_sig = genesis_coinbase_script_sig(Network::Testnet4);
+        assert_eq!(script_sig, genesis_block(Network::Testnet4).txdata[0].input[0].script_sig);
+        // A 76 byte message needs `OP_PUSHDATA1`.
+        assert_eq!(script_sig.as_bytes()[7..9], [OP_PUSHDATA1.to_u8(), 76]);
+    }
+
+    #[test]
+    fn verify_all_genesis() {
+        for network in ALL_NETWORKS {
+            let block = genesis_block(network);
+            let hash = block.block_hash();
+
+            assert_eq!(ChainHash::using_genesis_block_const(network).to_genesis_block_hash(), hash);
+            assert_eq!(ChainHash::using_genesis_block(network).to_genesis_block_hash(), hash);
+
+            assert!(block.header.is_genesis());
+            assert_eq!(block.header.validate_pow(block.header.target()), Ok(hash));
+
+            assert_eq!(block.txdata.len(), 1);
+            let coinbase = &block.txdata[0];
+            assert!(coinbase.is_coinbase());
+            assert_eq!(
+                block.header.merkle_root.to_byte_array(),
+                coinbase.compute_txid().to_byte_array()
+            );
+            assert!(block.check_merkle_root());
+            assert_eq!(coinbase.output.len(), 1);
+            assert_eq!(coinbase.output[0].value, Amount::from_int_btc(50));
+        }
+    }
+
+    #[test]
+    fn network_for_genesis_hash_known_networks() {
+        for network in ALL_NETWORKS {
+            let hash = genesis_block(network).block_hash();
+            assert_eq!(network_for_genesis_hash(hash), Some(network));
+        }
+        assert_eq!(network_for_genesis_hash(BlockHash::all_zeros()), None);
+
+        // The hash of the first mainnet block after genesis.
+        let block_1 = "00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048"
+            .parse::<BlockHash>()
+            .unwrap();
+        assert_eq!(network_for_genesis_hash(block_1), None);
+    }
 }
@@ -7,18 +7,24 @@
 
 use internals::ToU64 as _;
 
+use super::witness_program::WitnessProgram;
 use super::witness_version::WitnessVersion;
 use super::{
-    bytes_to_asm_fmt, Builder, Instruction, InstructionIndices, Instructions, PushBytes,
-    RedeemScriptSizeError, ScriptBuf, ScriptHash, WScriptHash, WitnessScriptSizeError,
+    bytes_to_asm_fmt, scriptint_parse, Builder, Instruction, InstructionIndices, Instructions,
+    Opcodes, PushBytes, RedeemScriptSizeError, ScriptBuf, ScriptHash, Template, WScriptHash,
+    WitnessScriptSizeError,
 };
 use crate::consensus::Encodable;
+use crate::constants::MAX_SCRIPT_SIZE;
+#[cfg(doc)]
+use crate::constants::MAX_STACK_ELEMENT_SIZE;
+use crate::locktime::absolute;
 use crate::opcodes::all::*;
 use crate::opcodes::{self, Opcode};
 use crate::policy::DUST_RELAY_TX_FEE;
 use crate::prelude::{sink, Box, DisplayHex, String, ToOwned, Vec};
 use crate::taproot::{LeafVersion, TapLeafHash};
-use crate::FeeRate;
+use crate::{FeeRate, Sequence};
 
 /// Bitcoin script slice.
 ///
@@ -194,6 +200,17 @@ fn witness_version(&self) -> Option<WitnessVersion> {
             WitnessVersion::try_from(ver_opcode).ok()
         }
 
+        /// Returns the witness program of the script, if any, assuming the script is a `scriptPubkey`.
+        ///
+        /// Returns `None` if the script is not a witness program (see [`Script::witness_version`])
+        /// or if the program violates the length rules of its version, i.e. a version 0 program
+        /// that is neither 20 (P2WPKH) nor 32 (P2WSH) bytes long.
+        #[inline]
+        fn witness_program(&self) -> Option<WitnessProgram> {
+            let version = self.witness_version()?;
+            WitnessProgram::new(version, &self.as_bytes()[2..]).ok()
+        }
+
         /// Checks whether a script pubkey is a P2SH output.
         #[inline]
         fn is_p2sh(&self) -> bool {
@@ -333,6 +350,23 @@ fn is_op_return(&self) -> bool {
         #[inline]
         fn is_standard_op_return(&self) -> bool { self.is_op_return() && self.0.len() <= 80 }
 
+        /// Returns the data of an OP_RETURN output, as created by [`ScriptBuf::new_op_return`].
+        ///
+        /// Returns `None` unless the script is `OP_RETURN` followed by exactly one data push.
+        ///
+        /// [`ScriptBuf::new_op_return`]: super::ScriptBufExt::new_op_return
+        fn op_return_data(&self) -> Option<&[u8]> {
+            self.matches_template(&[Template::Op(OP_RETURN), Template::Push])
+                .map(|captures| captures[0])
+        }
+
+        /// Checks whether this script exceeds the [`MAX_SCRIPT_SIZE`] consensus limit.
+        ///
+        /// Such a script can never be successfully executed. Note that this does not check the size
+        /// of individual pushes, see [`MAX_STACK_ELEMENT_SIZE`] for that limit.
+        #[inline]
+        fn is_too_large(&self) -> bool { self.0.len() > MAX_SCRIPT_SIZE }
+
         /// Checks whether a script is trivially known to have no satisfying input.
         ///
         /// This method has potentially confusing semantics and an unclear purpose, so it's going to be
@@ -480,6 +514,69 @@ fn instruction_indices_minimal(&self) -> InstructionIndices {
             InstructionIndices::from_instructions(self.instructions_minimal())
         }
 
+        /// Iterates over the opcodes of the script.
+        ///
+        /// Data pushes are yielded as the opcode used to push them and the pushed bytes are skipped,
+        /// so the genesis block output script yields `OP_PUSHBYTES_65` then `OP_CHECKSIG`. Iteration
+        /// stops at the first malformed instruction, use [`instructions`](Self::instructions) to
+        /// detect errors.
+        #[inline]
+        fn opcodes(&self) -> Opcodes { Opcodes::from_instructions(self.instructions()) }
+
+        /// Returns the data of the first push in the script.
+        ///
+        /// Opcodes before the push, like the `OP_RETURN` of a null data output, are skipped.
+        /// `OP_PUSHNUM_x` opcodes are not data pushes, but `OP_0` is a push of empty data. Returns
+        /// `None` if the script has no push or is malformed before the first one.
+        fn first_push(&self) -> Option<&[u8]> {
+            for instruction in self.instructions() {
+                if let Instruction::PushBytes(bytes) = instruction.ok()? {
+                    return Some(bytes.as_bytes());
+                }
+            }
+            None
+        }
+
+        /// Returns the data of the last push in the script.
+        ///
+        /// See [`first_push`](Self::first_push) for what counts as a push. Returns `None` if the
+        /// script has no push or is malformed anywhere.
+        fn last_push(&self) -> Option<&[u8]> {
+            let mut last = None;
+            for instruction in self.instructions() {
+                if let Instruction::PushBytes(bytes) = instruction.ok()? {
+                    last = Some(bytes.as_bytes());
+                }
+            }
+            last
+        }
+
+        /// Matches the script against `template`, returning the data of the captured pushes.
+        ///
+        /// The script must consist of exactly the instructions described by `template`, in order.
+        /// For example a p2pkh output matches `[Op(OP_DUP), Op(OP_HASH160), PushLen(20),
+        /// Op(OP_EQUALVERIFY), Op(OP_CHECKSIG)]`, capturing the pubkey hash. Pushes are not required
+        /// to be minimal. Returns `None` if the script does not match or is malformed.
+        fn matches_template(&self, template: &[Template]) -> Option<Vec<&[u8]>> {
+            let mut captures = Vec::new();
+            let mut instructions = self.instructions();
+            for expected in template {
+                match (expected, instructions.next()?.ok()?) {
+                    (Template::Op(op), Instruction::Op(got)) if *op == got => {}
+                    (Template::Push, Instruction::PushBytes(bytes)) =>
+                        captures.push(bytes.as_bytes()),
+                    (Template::PushLen(len), Instruction::PushBytes(bytes))
+                        if bytes.len() == *len =>
+                        captures.push(bytes.as_bytes()),
+                    _ => return None,
+                }
+            }
+            if instructions.next().is_some() {
+                return None;
+            }
+            Some(captures)
+        }
+
         /// Writes the human-readable assembly representation of the script to the formatter.
         fn fmt_asm(&self, f: &mut dyn fmt::Write) -> fmt::Result {
             bytes_to_asm_fmt(self.as_ref(), f)
@@ -503,6 +600,27 @@ fn to_hex_string(&self) -> String { self.as_bytes().to_lower_hex_string() }
         fn first_opcode(&self) -> Option<Opcode> {
             self.as_bytes().first().copied().map(From::from)
         }
+
+        /// Returns the lock time checked by the first `<n> OP_CHECKLOCKTIMEVERIFY` in the script.
+        ///
+        /// This matches the canonical `<n> OP_CLTV OP_DROP ...` pattern as well as scripts where
+        /// `OP_CLTV` ends a `VERIFY` chain. Returns `None` if the script does not contain such a
+        /// pattern, if the operand is not a minimally encoded non-negative number of at most 5
+        /// bytes, or if the script fails to parse before the pattern is found.
+        fn extract_cltv(&self) -> Option<absolute::LockTime> {
+            self.timelock_operand(OP_CLTV).map(absolute::LockTime::from_consensus)
+        }
+
+        /// Returns the sequence checked by the first `<n> OP_CHECKSEQUENCEVERIFY` in the script.
+        ///
+        /// This matches the canonical `<n> OP_CSV OP_DROP ...` pattern, see
+        /// [`Script::extract_cltv`] for details on which operands are accepted.
+        ///
+        /// Note that the returned [`Sequence`] may have the disable flag set, in which case
+        /// `OP_CSV` behaves as a `NOP`, use [`Sequence::is_relative_lock_time`] to check.
+        fn extract_csv(&self) -> Option<Sequence> {
+            self.timelock_operand(OP_CSV).map(Sequence::from_consensus)
+        }
     }
 }
 
@@ -571,6 +689,42 @@ fn count_sigops_internal(&self, accurate: bool) -> usize {
             n
         }
 
+        /// Returns the operand pushed immediately before the first occurrence of `timelock_op`.
+        ///
+        /// Timelock operands may be up to 5 bytes long (BIP-65), unlike other script numbers.
+        fn timelock_operand(&self, timelock_op: Opcode) -> Option<u32> {
+            let mut previous = None;
+            for instruction in self.instructions() {
+                let instruction = instruction.ok()?;
+                if instruction == Instruction::Op(timelock_op) {
+                    let n = match previous? {
+                        Instruction::PushBytes(bytes) => {
+                            let bytes = bytes.as_bytes();
+                            match bytes.split_last() {
+                                None => 0,
+                                Some((last, rest)) => {
+                                    if bytes.len() > 5 {
+                                        return None;
+                                    }
+                                    // Same minimality rule as `PushBytes::read_scriptint`.
+                                    if *last & 0x7f == 0
+                                        && rest.last().map_or(true, |b| b & 0x80 == 0)
+                                    {
+                                        return None;
+                                    }
+                                    scriptint_parse(bytes)
+                                }
+                            }
+                        }
+                        Instruction::Op(op) => op.decode_pushnum()?.into(),
+                    };
+                    return u32::try_from(n).ok();
+                }
+                previous = Some(instruction);
+            }
+            None
+        }
+
         /// Iterates the script to find the last opcode.
         ///
         /// Returns `None` is the instruction is data push or if the script is empty.
@@ -25,6 +25,11 @@ pub fn len(&self) -> usize { self.0.len() }
     /// Checks whether the script is the empty script.
     pub fn is_empty(&self) -> bool { self.0.is_empty() }
 
+    /// Checks whether the script built so far exceeds the consensus size limit.
+    ///
+    /// See [`Script::is_too_large`](crate::script::ScriptExt::is_too_large).
+    pub fn is_too_large(&self) -> bool { self.0.is_too_large() }
+
     /// Adds instructions to push an integer onto the stack.
     ///
     /// Integers are encoded as little-endian signed-magnitude numbers, but there are dedicated
@@ -99,6 +104,14 @@ pub fn push_sequence(self, sequence: Sequence) -> Builder {
         self.push_int(sequence.to_consensus_u32().into())
     }
 
+    /// Adds instructions to push a block height onto the stack, as required by [BIP-34].
+    ///
+    /// The height is pushed as a minimally encoded integer, the same way Bitcoin Core does. Note
+    /// that this means heights 0 through 16 are pushed using an opcode rather than a data push.
+    ///
+    /// [BIP-34]: <https://github.com/bitcoin/bips/blob/master/bip-0034.mediawiki>
+    pub fn push_height(self, height: u32) -> Builder { self.push_int(height.into()) }
+
     /// Converts the `Builder` into `ScriptBuf`.
     pub fn into_script(self) -> ScriptBuf { self.0 }
 
@@ -14,6 +14,22 @@ pub enum Instruction<'a> {
     Op(Opcode),
 }
 
+/// An element of a script template, see [`ScriptExt::matches_template`].
+///
+/// [`ScriptExt::matches_template`]: super::ScriptExt::matches_template
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum Template {
+    /// Exactly this non-push opcode.
+    ///
+    /// Note that `OP_0` (`OP_PUSHBYTES_0`) is an empty data push, match it with `PushLen(0)`.
+    Op(Opcode),
+    /// A data push of any length, the data is captured.
+    Push,
+    /// A data push of exactly this many bytes, the data is captured.
+    PushLen(usize),
+}
+
 impl<'a> Instruction<'a> {
     /// Returns the opcode if the instruction is not a data push.
     pub fn opcode(&self) -> Option<Opcode> {
@@ -275,3 +291,43 @@ fn nth(&mut self, n: usize) -> Option<Self::Item> {
 }
 
 impl core::iter::FusedIterator for InstructionIndices<'_> {}
+
+/// Iterator over the opcodes of a script, see [`ScriptExt::opcodes`].
+///
+/// Data pushes are returned as their push opcode (e.g. `OP_PUSHBYTES_33` or `OP_PUSHDATA1`), the
+/// pushed bytes themselves are skipped. Iteration stops at the first malformed instruction.
+///
+/// [`ScriptExt::opcodes`]: super::ScriptExt::opcodes
+#[derive(Debug, Clone)]
+pub struct Opcodes<'a> {
+    instructions: Instructions<'a>,
+}
+
+impl<'a> Opcodes<'a> {
+    /// Creates `Self` from an instruction iterator.
+    pub(super) fn from_instructions(instructions: Instructions<'a>) -> Self {
+        Opcodes { instructions }
+    }
+
+    /// Views the remaining script as a slice.
+    #[inline]
+    pub fn as_script(&self) -> &'a Script { self.instructions.as_script() }
+}
+
+impl<'a> Iterator for Opcodes<'a> {
+    type Item = Opcode;
+
+    fn next(&mut self) -> Option<Opcode> {
+        // Every instruction, push or not, starts with its opcode byte.
+        let byte = *self.instructions.data.as_slice().first()?;
+        match self.instructions.next()? {
+            Ok(_) => Some(Opcode::from(byte)),
+            Err(_) => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (0, self.instructions.size_hint().1) }
+}
+
+impl core::iter::FusedIterator for Opcodes<'_> {}
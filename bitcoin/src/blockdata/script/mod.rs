@@ -216,6 +216,22 @@ pub fn p2wpkh_script_code(wpkh: WPubkeyHash) -> ScriptBuf {
         .into_script()
 }
 
+/// Creates a coinbase `scriptSig` committing to the block `height` as required by [BIP-34].
+///
+/// The height push is followed by `extra`, which is appended verbatim (e.g. an extra nonce or a
+/// miner tag). Consensus requires a coinbase `scriptSig` to be between 2 and 100 bytes long, it
+/// is up to the caller to choose `extra` accordingly.
+///
+/// This is the inverse of [`Block::bip34_block_height`] for heights above 16.
+///
+/// [BIP-34]: <https://github.com/bitcoin/bips/blob/master/bip-0034.mediawiki>
+/// [`Block::bip34_block_height`]: crate::block::Block::bip34_block_height
+pub fn coinbase_script(height: u32, extra: &[u8]) -> ScriptBuf {
+    let mut bytes = Builder::new().push_height(height).into_bytes();
+    bytes.extend_from_slice(extra);
+    ScriptBuf::from_bytes(bytes)
+}
+
 /// Encodes an integer in script(minimal CScriptNum) format.
 ///
 /// Writes bytes into the buffer and returns the number of bytes written.
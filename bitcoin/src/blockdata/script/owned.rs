@@ -8,7 +8,9 @@
 use hex::FromHex;
 use internals::ToU64 as _;
 
-use super::{opcode_to_verify, Builder, Instruction, PushBytes, Script, ScriptExtPriv as _};
+use super::{
+    opcode_to_verify, Builder, Instruction, PushBytes, Script, ScriptExt as _, ScriptExtPriv as _,
+};
 use crate::opcodes::all::*;
 use crate::opcodes::{self, Opcode};
 use crate::prelude::{Box, Vec};
@@ -102,6 +104,22 @@ fn new_op_return<T: AsRef<PushBytes>>(data: T) -> Self {
             Builder::new().push_opcode(OP_RETURN).push_slice(data).into_script()
         }
 
+        /// Generates an OP_RETURN-type of scriptPubkey for the given data if it is standard.
+        ///
+        /// Returns `None` if the script would not pass [`is_standard_op_return`], i.e. if it is
+        /// longer than 80 bytes. The `OP_RETURN` and the push opcode count towards this so at most
+        /// 77 bytes of data fit.
+        ///
+        /// [`is_standard_op_return`]: super::ScriptExt::is_standard_op_return
+        fn new_standard_op_return<T: AsRef<PushBytes>>(data: T) -> Option<Self> {
+            let script = Self::new_op_return(data);
+            if script.is_standard_op_return() {
+                Some(script)
+            } else {
+                None
+            }
+        }
+
         /// Creates a [`ScriptBuf`] from a hex string.
         fn from_hex(s: &str) -> Result<ScriptBuf, hex::HexToBytesError> {
             let v = Vec::from_hex(s)?;
@@ -118,6 +136,18 @@ fn push_slice<T: AsRef<PushBytes>>(&mut self, data: T) {
             self.push_slice_no_opt(data);
         }
 
+        /// Appends the contents of `other` to the end of this script.
+        ///
+        /// The bytes are copied verbatim so each push in `other` stays a separate push, nothing is
+        /// merged or re-encoded. If `self` ends in a truncated push (see
+        /// [`Error::EarlyEndOfScript`]) the appended bytes are consumed by it, so this should only
+        /// be used with well-formed scripts.
+        ///
+        /// [`Error::EarlyEndOfScript`]: super::Error::EarlyEndOfScript
+        fn push_script(&mut self, other: &Script) {
+            self.as_byte_vec().extend_from_slice(other.as_bytes());
+        }
+
         /// Add a single instruction to the script.
         ///
         /// # Panics
@@ -2,8 +2,10 @@
 
 //! Contains `PushBytes` & co
 
+use core::fmt;
 use core::ops::{Deref, DerefMut};
 
+use crate::constants::MAX_STACK_ELEMENT_SIZE;
 use crate::prelude::{Borrow, BorrowMut};
 use crate::script;
 
@@ -39,6 +41,9 @@ fn check_limit(len: usize) -> Result<(), PushBytesError> {
     /// The encoding of Bitcoin script restricts data pushes to be less than 2^32 bytes long.
     /// This type represents slices that are guaranteed to be within the limit so they can be put in
     /// the script safely.
+    ///
+    /// Comparison is lexicographic over the bytes, the same as for `[u8]`, so e.g. pushed public
+    /// keys sort the way BIP-67 requires.
     #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
     #[repr(transparent)]
     pub struct PushBytes([u8]);
@@ -49,7 +54,10 @@ impl PushBytes {
         /// # Safety
         ///
         /// The caller is responsible for checking that the length is less than the 2^32.
-        unsafe fn from_slice_unchecked(bytes: &[u8]) -> &Self {
+        ///
+        /// Use this to view already validated script elements (e.g. ones obtained by parsing a
+        /// script) as `PushBytes` without copying them into a [`PushBytesBuf`].
+        pub unsafe fn from_slice_unchecked(bytes: &[u8]) -> &Self {
             // SAFETY: The caller must guarantee that bytes.len() < 2^32.
             // If that is the case the conversion is sound because &[u8] and &PushBytes
             // have the same layout (because of #[repr(transparent)] on PushBytes).
@@ -211,6 +219,8 @@ fn as_mut(&mut self) -> &mut PushBytes {
     }
 
     /// Owned, growable counterpart to `PushBytes`.
+    ///
+    /// Compares the same way as `PushBytes`.
     #[derive(Default, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
     pub struct PushBytesBuf(Vec<u8>);
 
@@ -307,6 +317,23 @@ fn to_owned(&self) -> Self::Owned { PushBytesBuf(self.0.to_owned()) }
 }
 
 impl PushBytes {
+    /// Creates `&PushBytes` from a slice that must fit in a single stack element.
+    ///
+    /// Unlike the `TryFrom<&[u8]>` conversion, which only enforces the 2^32 encoding limit, this
+    /// rejects slices longer than [`MAX_STACK_ELEMENT_SIZE`] bytes, the consensus limit for a
+    /// single push.
+    ///
+    /// # Errors
+    ///
+    /// If `bytes` is longer than [`MAX_STACK_ELEMENT_SIZE`].
+    pub fn from_stack_element(bytes: &[u8]) -> Result<&Self, StackElementSizeError> {
+        if bytes.len() > MAX_STACK_ELEMENT_SIZE {
+            return Err(StackElementSizeError { len: bytes.len() });
+        }
+        // SAFETY: MAX_STACK_ELEMENT_SIZE < 2^32.
+        Ok(unsafe { Self::from_slice_unchecked(bytes) })
+    }
+
     /// Returns the number of bytes in buffer.
     pub fn len(&self) -> usize { self.as_bytes().len() }
 
@@ -477,3 +504,30 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 impl std::error::Error for PushBytesError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> { None }
 }
+
+/// Error returned when a slice is too large to be a single stack element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StackElementSizeError {
+    /// How long the input was.
+    len: usize,
+}
+
+impl StackElementSizeError {
+    /// Returns the length of the rejected input.
+    pub fn input_len(&self) -> usize { self.len }
+}
+
+impl fmt::Display for StackElementSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "stack element of {} bytes exceeds the limit of {} bytes",
+            self.len, MAX_STACK_ELEMENT_SIZE
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StackElementSizeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> { None }
+}
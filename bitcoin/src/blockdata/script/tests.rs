@@ -8,7 +8,8 @@
 };
 use crate::consensus::encode::{deserialize, serialize};
 use crate::crypto::key::{PublicKey, XOnlyPublicKey};
-use crate::FeeRate;
+use crate::locktime::absolute;
+use crate::{FeeRate, Sequence};
 
 #[test]
 #[rustfmt::skip]
@@ -421,6 +422,28 @@ fn standard_op_return_test() {
         .is_standard_op_return());
 }
 
+#[test]
+fn standard_op_return_constructor_and_data() {
+    let data = [0xab; 77];
+    let script = ScriptBuf::new_standard_op_return(<&PushBytes>::try_from(&data[..]).unwrap())
+        .expect("77 bytes of data are standard");
+    assert_eq!(script.len(), 80);
+    assert!(script.is_standard_op_return());
+    assert_eq!(script.op_return_data(), Some(&data[..]));
+
+    let data = [0xab; 78];
+    let push = <&PushBytes>::try_from(&data[..]).unwrap();
+    assert_eq!(ScriptBuf::new_standard_op_return(push), None);
+    assert_eq!(ScriptBuf::new_op_return(push).op_return_data(), Some(&data[..]));
+
+    assert_eq!(ScriptBuf::new_op_return(PushBytes::empty()).op_return_data(), Some(&[][..]));
+    // Not a single push after the `OP_RETURN`.
+    assert_eq!(ScriptBuf::from_hex("6a").unwrap().op_return_data(), None);
+    assert_eq!(ScriptBuf::from_hex("6a0101515101").unwrap().op_return_data(), None);
+    assert_eq!(ScriptBuf::from_hex("6a0101").unwrap().op_return_data(), Some(&[0x01][..]));
+    assert_eq!(ScriptBuf::from_hex("0101").unwrap().op_return_data(), None);
+}
+
 #[test]
 fn multisig() {
     // First multisig? 1-of-2
@@ -925,3 +948,427 @@ fn instruction_script_num_parse() {
         Some(Ok(Instruction::PushBytes(PushBytes::empty()))),
     );
 }
+
+#[test]
+fn script_extract_cltv() {
+    let lock_time = absolute::LockTime::from_consensus(800_000);
+    let script = Builder::new()
+        .push_lock_time(lock_time)
+        .push_opcode(OP_CLTV)
+        .push_opcode(OP_DROP)
+        .push_opcode(OP_CHECKSIG)
+        .into_script();
+    assert_eq!(script.extract_cltv(), Some(lock_time));
+    assert_eq!(script.extract_csv(), None);
+
+    // Lock times above `i32::MAX` need a 5 byte push.
+    let lock_time = absolute::LockTime::from_consensus(0xffff_fffe);
+    let script = Builder::new().push_lock_time(lock_time).push_opcode(OP_CLTV).into_script();
+    assert_eq!(script.extract_cltv(), Some(lock_time));
+
+    let script = Builder::new().push_int(16).push_opcode(OP_CLTV).into_script();
+    assert_eq!(script.extract_cltv(), Some(absolute::LockTime::from_consensus(16)));
+
+    // Negative operands are rejected.
+    let script = Builder::new().push_int(-1).push_opcode(OP_CLTV).into_script();
+    assert_eq!(script.extract_cltv(), None);
+
+    // Non-minimal operands are rejected.
+    let script = Builder::new().push_slice([0x01, 0x00]).push_opcode(OP_CLTV).into_script();
+    assert_eq!(script.extract_cltv(), None);
+
+    // No operand.
+    let script = Builder::new().push_opcode(OP_CLTV).into_script();
+    assert_eq!(script.extract_cltv(), None);
+}
+
+#[test]
+fn script_extract_csv() {
+    let sequence = Sequence::from_height(144);
+    let script = Builder::new()
+        .push_opcode(OP_CHECKSIGVERIFY)
+        .push_sequence(sequence)
+        .push_opcode(OP_CSV)
+        .into_script();
+    assert_eq!(script.extract_csv(), Some(sequence));
+    assert_eq!(script.extract_cltv(), None);
+
+    let sequence = Sequence::from_512_second_intervals(10);
+    let script = Builder::new()
+        .push_sequence(sequence)
+        .push_opcode(OP_CSV)
+        .push_opcode(OP_DROP)
+        .into_script();
+    assert_eq!(script.extract_csv(), Some(sequence));
+}
+
+#[test]
+fn script_witness_program() {
+    use crate::script::witness_program::WitnessProgram;
+    use crate::script::witness_version::WitnessVersion;
+
+    let p2wpkh = Builder::new().push_int(0).push_slice([42; 20]).into_script();
+    let program = p2wpkh.witness_program().unwrap();
+    assert_eq!(program.version(), WitnessVersion::V0);
+    assert!(program.is_p2wpkh());
+
+    let p2wsh = Builder::new().push_int(0).push_slice([42; 32]).into_script();
+    let program = p2wsh.witness_program().unwrap();
+    assert_eq!(program, WitnessProgram::new(WitnessVersion::V0, &[42; 32]).unwrap());
+    assert!(program.is_p2wsh());
+
+    let p2tr = Builder::new().push_int(1).push_slice([42; 32]).into_script();
+    let program = p2tr.witness_program().unwrap();
+    assert_eq!(p2tr.witness_version(), Some(WitnessVersion::V1));
+    assert!(program.is_p2tr());
+    assert_eq!(program.program().as_bytes(), &[42; 32]);
+
+    // Version 0 programs must be 20 or 32 bytes long.
+    let invalid_v0 = Builder::new().push_int(0).push_slice([42; 21]).into_script();
+    assert!(invalid_v0.is_witness_program());
+    assert_eq!(invalid_v0.witness_program(), None);
+
+    // Other lengths are allowed for future versions.
+    let v1_short = Builder::new().push_int(1).push_slice([42; 2]).into_script();
+    assert_eq!(v1_short.witness_program().map(|p| p.is_p2tr()), Some(false));
+
+    let p2pkh = Builder::new()
+        .push_opcode(OP_DUP)
+        .push_opcode(OP_HASH160)
+        .push_slice([42; 20])
+        .push_opcode(OP_EQUALVERIFY)
+        .push_opcode(OP_CHECKSIG)
+        .into_script();
+    assert_eq!(p2pkh.witness_version(), None);
+    assert_eq!(p2pkh.witness_program(), None);
+}
+
+#[test]
+fn script_is_too_large() {
+    use crate::constants::{MAX_SCRIPT_SIZE, MAX_STACK_ELEMENT_SIZE};
+
+    assert!(!Script::new().is_too_large());
+    assert!(!ScriptBuf::from_bytes(vec![OP_NOP.to_u8(); MAX_SCRIPT_SIZE]).is_too_large());
+    assert!(ScriptBuf::from_bytes(vec![OP_NOP.to_u8(); MAX_SCRIPT_SIZE + 1]).is_too_large());
+
+    let element = [0u8; MAX_STACK_ELEMENT_SIZE];
+    let element = <&PushBytes>::try_from(&element[..]).unwrap();
+    let mut builder = Builder::new();
+    // Each push adds `OP_PUSHDATA2` and two length bytes to the element.
+    for _ in 0..(MAX_SCRIPT_SIZE / (MAX_STACK_ELEMENT_SIZE + 3)) {
+        builder = builder.push_slice(element);
+        assert!(!builder.is_too_large());
+    }
+    builder = builder.push_slice(element);
+    assert!(builder.is_too_large());
+    assert!(builder.as_script().is_too_large());
+}
+
+#[test]
+fn push_bytes_from_stack_element() {
+    use crate::constants::MAX_STACK_ELEMENT_SIZE;
+
+    let max = [0xabu8; MAX_STACK_ELEMENT_SIZE + 1];
+    let element = PushBytes::from_stack_element(&max[..MAX_STACK_ELEMENT_SIZE]).unwrap();
+    assert_eq!(element.as_bytes(), &max[..MAX_STACK_ELEMENT_SIZE]);
+
+    let err = PushBytes::from_stack_element(&max).unwrap_err();
+    assert_eq!(err.input_len(), MAX_STACK_ELEMENT_SIZE + 1);
+    // The plain conversion only enforces the encoding limit.
+    assert!(<&PushBytes>::try_from(&max[..]).is_ok());
+
+    let data = hex!("0102030405");
+    let rebuilt = Builder::new().push_slice(PushBytes::from_stack_element(&data).unwrap());
+    assert_eq!(rebuilt.as_bytes(), &hex!("050102030405"));
+}
+
+#[test]
+fn script_buf_push_script() {
+    let sig_part = Builder::new().push_slice([0xaa; 3]).push_opcode(OP_CHECKSIG).into_script();
+    let lock_part = Builder::new().push_int(144).push_opcode(OP_CSV).into_script();
+
+    let mut script = sig_part.clone();
+    script.push_script(&lock_part);
+    assert_eq!(script.as_bytes(), &hex!("03aaaaaaac029000b2"));
+    assert_eq!(script.instructions().count(), 4);
+
+    // Joining two pushes keeps them as two separate pushes.
+    let mut pushes = Builder::new().push_slice([0x01]).into_script();
+    pushes.push_script(&Builder::new().push_slice([0x02, 0x03]).into_script());
+    let instructions: Vec<_> = pushes.instructions().map(Result::unwrap).collect();
+    assert_eq!(instructions.len(), 2);
+    assert_eq!(instructions[1].push_bytes().unwrap().as_bytes(), &[0x02, 0x03]);
+
+    let mut extended = sig_part;
+    extended.extend(lock_part.instructions().map(Result::unwrap));
+    assert_eq!(extended, script);
+}
+
+#[test]
+fn wrap_script_in_p2sh_and_p2wsh() {
+    use crate::constants::{genesis_block, MAX_REDEEM_SCRIPT_SIZE, MAX_WITNESS_SCRIPT_SIZE};
+    use crate::Network;
+
+    let p2pk = genesis_block(Network::Bitcoin).txdata[0].output[0].script_pubkey.clone();
+    assert!(p2pk.is_p2pk());
+
+    let p2sh = p2pk.to_p2sh().unwrap();
+    assert!(p2sh.is_p2sh());
+    assert_eq!(p2sh.as_bytes(), &hex!("a9148424e7542477ef1a76cbab88d4b177d2fb5a96c187"));
+
+    let p2wsh = p2pk.to_p2wsh().unwrap();
+    assert!(p2wsh.is_p2wsh());
+    assert_eq!(
+        p2wsh.as_bytes(),
+        &hex!("00203318537dfb3135df9f3d950dbdf8a7ae68dd7c7dfef61ed17963ff80f3850474")
+    );
+
+    let script = |len| ScriptBuf::from_bytes(vec![OP_NOP.to_u8(); len]);
+    assert!(script(MAX_REDEEM_SCRIPT_SIZE).to_p2sh().is_ok());
+    assert_eq!(
+        script(MAX_REDEEM_SCRIPT_SIZE + 1).to_p2sh(),
+        Err(RedeemScriptSizeError { size: MAX_REDEEM_SCRIPT_SIZE + 1 })
+    );
+    assert!(script(MAX_WITNESS_SCRIPT_SIZE).to_p2wsh().is_ok());
+    assert_eq!(
+        script(MAX_WITNESS_SCRIPT_SIZE + 1).to_p2wsh(),
+        Err(WitnessScriptSizeError { size: MAX_WITNESS_SCRIPT_SIZE + 1 })
+    );
+}
+
+#[test]
+fn script_opcodes() {
+    use crate::constants::genesis_block;
+    use crate::Network;
+
+    let genesis_out = &genesis_block(Network::Bitcoin).txdata[0].output[0].script_pubkey;
+    assert_eq!(genesis_out.opcodes().collect::<Vec<_>>(), [OP_PUSHBYTES_65, OP_CHECKSIG]);
+
+    let p2pkh = ScriptBuf::from_hex("76a914f7f1a7f2b9e3c6ac647d69b4ab0e3f6a3bd4f02588ac").unwrap();
+    assert_eq!(
+        p2pkh.opcodes().collect::<Vec<_>>(),
+        [OP_DUP, OP_HASH160, OP_PUSHBYTES_20, OP_EQUALVERIFY, OP_CHECKSIG]
+    );
+
+    // Non-minimal pushes keep their opcode.
+    let pushdata = ScriptBuf::from_bytes(vec![OP_PUSHDATA1.to_u8(), 0x01, 0xff, OP_DROP.to_u8()]);
+    assert_eq!(pushdata.opcodes().collect::<Vec<_>>(), [OP_PUSHDATA1, OP_DROP]);
+
+    // Iteration ends at a truncated push.
+    let truncated = ScriptBuf::from_bytes(vec![OP_NOP.to_u8(), OP_PUSHBYTES_2.to_u8(), 0x01]);
+    assert_eq!(truncated.opcodes().collect::<Vec<_>>(), [OP_NOP]);
+}
+
+#[test]
+fn builder_push_height() {
+    assert_eq!(Builder::new().push_height(0).as_bytes(), &[OP_PUSHBYTES_0.to_u8()]);
+    assert_eq!(Builder::new().push_height(16).as_bytes(), &[OP_PUSHNUM_16.to_u8()]);
+    assert_eq!(Builder::new().push_height(17).as_bytes(), &[0x01, 0x11]);
+    // The sign bit forces an extra byte.
+    assert_eq!(Builder::new().push_height(128).as_bytes(), &[0x02, 0x80, 0x00]);
+    // First block height at which BIP-34 was enforced on mainnet.
+    assert_eq!(Builder::new().push_height(227_931).as_bytes(), &hex!("035b7a03"));
+}
+
+#[test]
+fn coinbase_script_height() {
+    let script = coinbase_script(227_931, b"/tag/");
+    assert_eq!(script.as_bytes(), &hex!("035b7a032f7461672f"));
+
+    match script.instructions_minimal().next() {
+        Some(Ok(Instruction::PushBytes(b))) => assert_eq!(b.read_scriptint(), Ok(227_931)),
+        _ => panic!("expected a height push"),
+    }
+
+    assert_eq!(coinbase_script(1, &[]).as_bytes(), &[OP_PUSHNUM_1.to_u8()]);
+}
+
+#[test]
+fn witness_program_to_script_pubkey() {
+    use crate::script::witness_program::{Error, WitnessProgram};
+    use crate::script::witness_version::WitnessVersion;
+
+    assert_eq!(WitnessProgram::new(WitnessVersion::V1, &[0; 1]), Err(Error::InvalidLength(1)));
+    assert_eq!(WitnessProgram::new(WitnessVersion::V1, &[0; 41]), Err(Error::InvalidLength(41)));
+    assert_eq!(
+        WitnessProgram::new(WitnessVersion::V0, &[0; 21]),
+        Err(Error::InvalidSegwitV0Length(21))
+    );
+    assert!(WitnessProgram::new(WitnessVersion::V1, &[0; 2]).is_ok());
+    assert!(WitnessProgram::new(WitnessVersion::V16, &[0; 40]).is_ok());
+
+    let program = WitnessProgram::new(WitnessVersion::V0, &[42; 20]).unwrap();
+    let script = program.to_script_pubkey();
+    assert_eq!(script, ScriptBuf::new_p2wpkh(WPubkeyHash::from_byte_array([42; 20])));
+    assert_eq!(script.witness_program(), Some(program));
+
+    let program = WitnessProgram::new(WitnessVersion::V16, &[42; 2]).unwrap();
+    let script = program.to_script_pubkey();
+    assert_eq!(script.as_bytes(), &[OP_PUSHNUM_16.to_u8(), OP_PUSHBYTES_2.to_u8(), 42, 42]);
+    assert_eq!(script.witness_program(), Some(program));
+}
+
+#[test]
+fn script_parse_multisig() {
+    let keys: Vec<PublicKey> = [
+        "03df154ebfcf29d29cc10d5c2565018bce2d9edbab267c31d2caf44a63056cf99f",
+        "033bc8c83c52df5712229a2f72206d90192366c36428cb0c12b6af98324d97bfbc",
+        "048d5141948c1702e8c95f438815794b87f706a8d4cd2bffad1dc1570971032c9b6042a0431ded2478b5c9cf2d81c124a5e57347a3c63ef0e7716cf54d613ba183",
+    ]
+    .iter()
+    .map(|k| k.parse().unwrap())
+    .collect();
+
+    let multisig = |m: i64, keys: &[PublicKey], n: i64| {
+        let mut builder = Builder::new().push_int(m);
+        for key in keys {
+            builder = builder.push_key(*key);
+        }
+        builder.push_int(n).push_opcode(OP_CHECKMULTISIG).into_script()
+    };
+
+    let script = multisig(2, &keys, 3);
+    assert!(script.is_multisig());
+    assert_eq!(script.parse_multisig(), Some((2, keys.clone())));
+    assert_eq!(multisig(1, &keys[..1], 1).parse_multisig(), Some((1, keys[..1].to_vec())));
+
+    // Key count does not match `n`.
+    assert_eq!(multisig(2, &keys, 2).parse_multisig(), None);
+    // Threshold above the number of keys.
+    assert_eq!(multisig(3, &keys[..2], 2).parse_multisig(), None);
+    // A zero threshold is not a small integer opcode.
+    assert_eq!(multisig(0, &keys, 3).parse_multisig(), None);
+
+    // Wrong or trailing opcodes.
+    let mut bytes = script.to_bytes();
+    *bytes.last_mut().unwrap() = OP_CHECKMULTISIGVERIFY.to_u8();
+    assert_eq!(ScriptBuf::from_bytes(bytes).parse_multisig(), None);
+    let mut bytes = script.to_bytes();
+    bytes.push(OP_NOP.to_u8());
+    assert_eq!(ScriptBuf::from_bytes(bytes).parse_multisig(), None);
+
+    // A push that is not a valid public key.
+    let invalid = Builder::new()
+        .push_int(1)
+        .push_slice([0x05; 33])
+        .push_int(1)
+        .push_opcode(OP_CHECKMULTISIG)
+        .into_script();
+    assert!(invalid.is_multisig());
+    assert_eq!(invalid.parse_multisig(), None);
+}
+
+#[test]
+fn script_first_and_last_push() {
+    use crate::constants::genesis_block;
+    use crate::Network;
+
+    let genesis = genesis_block(Network::Bitcoin);
+    let output = &genesis.txdata[0].output[0].script_pubkey;
+    let pubkey = output.first_push().unwrap();
+    assert_eq!(pubkey.len(), 65);
+    assert_eq!(pubkey[0], 0x04);
+    assert_eq!(output.last_push(), Some(pubkey));
+
+    // The coinbase pushes the bits, a 4 then the headline.
+    let script_sig = &genesis.txdata[0].input[0].script_sig;
+    assert_eq!(script_sig.first_push(), Some(&[0xff, 0xff, 0x00, 0x1d][..]));
+    assert!(script_sig.last_push().unwrap().starts_with(b"The Times"));
+
+    let null_data = Builder::new()
+        .push_opcode(OP_RETURN)
+        .push_slice(b"abc")
+        .push_int(1)
+        .push_slice(b"def")
+        .into_script();
+    assert_eq!(null_data.first_push(), Some(&b"abc"[..]));
+    assert_eq!(null_data.last_push(), Some(&b"def"[..]));
+
+    assert_eq!(ScriptBuf::new().first_push(), None);
+    assert_eq!(Builder::new().push_int(1).into_script().first_push(), None);
+    assert_eq!(Builder::new().push_int(0).into_script().first_push(), Some(&[][..]));
+
+    // A truncated push is malformed.
+    let truncated =
+        ScriptBuf::from_bytes(vec![OP_PUSHBYTES_1.to_u8(), 0x01, OP_PUSHBYTES_2.to_u8()]);
+    assert_eq!(truncated.first_push(), Some(&[0x01][..]));
+    assert_eq!(truncated.last_push(), None);
+}
+
+#[test]
+fn script_matches_template() {
+    use super::Template::*;
+    use crate::PubkeyHash;
+
+    const P2PKH: [Template; 5] =
+        [Op(OP_DUP), Op(OP_HASH160), PushLen(20), Op(OP_EQUALVERIFY), Op(OP_CHECKSIG)];
+
+    let hash = [0xab; 20];
+    let p2pkh = ScriptBuf::new_p2pkh(PubkeyHash::from_byte_array(hash));
+    assert_eq!(p2pkh.matches_template(&P2PKH), Some(vec![&hash[..]]));
+    assert_eq!(p2pkh.matches_template(&[Op(OP_DUP)]), None);
+    assert_eq!(p2pkh.matches_template(&P2PKH[..4]), None);
+
+    let p2sh = ScriptBuf::new_p2sh(ScriptHash::from_byte_array(hash));
+    assert_eq!(p2sh.matches_template(&P2PKH), None);
+    assert_eq!(p2sh.matches_template(&[Op(OP_HASH160), Push, Op(OP_EQUAL)]), Some(vec![&hash[..]]));
+    assert_eq!(p2sh.matches_template(&[Op(OP_HASH160), PushLen(32), Op(OP_EQUAL)]), None);
+
+    // `OP_0` is an empty push, not an opcode.
+    let p2wpkh = ScriptBuf::new_p2wpkh(WPubkeyHash::from_byte_array(hash));
+    assert_eq!(p2wpkh.matches_template(&[Op(OP_PUSHBYTES_0), PushLen(20)]), None);
+    assert_eq!(p2wpkh.matches_template(&[PushLen(0), PushLen(20)]), Some(vec![&[][..], &hash[..]]));
+
+    let null_data = Builder::new().push_opcode(OP_RETURN).push_slice(b"abc").into_script();
+    assert_eq!(null_data.matches_template(&[Op(OP_RETURN), Push]), Some(vec![&b"abc"[..]]));
+
+    assert_eq!(ScriptBuf::new().matches_template(&[]), Some(vec![]));
+    let truncated = ScriptBuf::from_bytes(vec![OP_RETURN.to_u8(), OP_PUSHBYTES_2.to_u8(), 0x01]);
+    assert_eq!(truncated.matches_template(&[Op(OP_RETURN), Push]), None);
+}
+
+#[test]
+fn push_bytes_ordering() {
+    let keys = [
+        hex!("03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556"),
+        hex!("02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f"),
+        hex!("02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8"),
+    ];
+    let mut pushes =
+        keys.iter().map(|key| <&PushBytes>::try_from(&key[..]).unwrap()).collect::<Vec<_>>();
+    pushes.sort();
+    let mut sorted = keys;
+    sorted.sort();
+    assert!(pushes.iter().zip(&sorted).all(|(push, key)| push.as_bytes() == key));
+    assert_eq!(pushes[0].as_bytes(), keys[1]);
+
+    let mut bufs = pushes.iter().map(|push| (*push).to_owned()).collect::<Vec<PushBytesBuf>>();
+    bufs.reverse();
+    bufs.sort();
+    assert!(bufs.iter().zip(&pushes).all(|(buf, push)| buf.as_push_bytes() == *push));
+
+    // A prefix sorts before the longer push.
+    let short = <&PushBytes>::try_from(&[0x02][..]).unwrap();
+    assert!(short < pushes[0]);
+    assert!(PushBytes::empty() < short);
+}
+
+#[test]
+fn p2wpkh_script_code() {
+    use crate::PubkeyHash;
+
+    // The native P2WPKH example from BIP-143.
+    let spk = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+    let want = ScriptBuf::from_hex("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap();
+    assert_eq!(spk.p2wpkh_script_code(), Some(want.clone()));
+    let wpkh = WPubkeyHash::from_byte_array(hex!("1d0f172a0ecb48aee1be1f2687d2963ae33f71a1"));
+    assert_eq!(super::p2wpkh_script_code(wpkh), want);
+
+    // The script code looks like the P2PKH script pubkey of the same hash.
+    let p2pkh = ScriptBuf::new_p2pkh(PubkeyHash::from_byte_array(wpkh.to_byte_array()));
+    assert_eq!(want, p2pkh);
+
+    assert_eq!(p2pkh.p2wpkh_script_code(), None);
+    assert_eq!(ScriptBuf::new_p2wsh(spk.wscript_hash().unwrap()).p2wpkh_script_code(), None);
+    assert_eq!(ScriptBuf::new().p2wpkh_script_code(), None);
+}
@@ -13,7 +13,8 @@
 use secp256k1::{Secp256k1, Verification};
 
 use super::witness_version::WitnessVersion;
-use super::{PushBytes, Script, WScriptHash, WitnessScriptSizeError};
+use super::{PushBytes, Script, ScriptBuf, WScriptHash, WitnessScriptSizeError};
+use crate::address::script_pubkey::ScriptBufExt as _;
 use crate::crypto::key::{CompressedPublicKey, TapTweak, TweakedPublicKey, UntweakedPublicKey};
 use crate::script::ScriptExt as _;
 use crate::taproot::TapNodeHash;
@@ -127,6 +128,11 @@ pub fn is_p2wsh(&self) -> bool {
 
     /// Returns true if this witness program is for a P2TR output.
     pub fn is_p2tr(&self) -> bool { self.version == WitnessVersion::V1 && self.program.len() == 32 }
+
+    /// Returns the `scriptPubkey` that locks an output to this witness program.
+    ///
+    /// This is the version opcode followed by a single push of the program bytes.
+    pub fn to_script_pubkey(&self) -> ScriptBuf { ScriptBuf::new_witness_program(self) }
 }
 
 /// Witness program error.
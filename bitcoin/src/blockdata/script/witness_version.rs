@@ -70,6 +70,29 @@ impl WitnessVersion {
     /// version in bitcoin script. Thus, there is no function to directly convert witness version
     /// into a byte since the conversion requires context (bitcoin script or just a version number).
     pub fn to_num(self) -> u8 { self as u8 }
+
+    /// Returns the checksum variant used when encoding an address for this witness version.
+    ///
+    /// Version 0 witness programs are encoded using bech32 ([BIP173]), all later versions use
+    /// bech32m ([BIP350]).
+    ///
+    /// [BIP173]: <https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki>
+    /// [BIP350]: <https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki>
+    pub fn encoding_variant(self) -> Bech32Variant {
+        match self {
+            WitnessVersion::V0 => Bech32Variant::Bech32,
+            _ => Bech32Variant::Bech32m,
+        }
+    }
+}
+
+/// The checksum variant of a segwit address encoding.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Bech32Variant {
+    /// The original bech32 checksum as defined by BIP173, used for version 0 witness programs.
+    Bech32,
+    /// The bech32m checksum as defined by BIP350, used for version 1+ witness programs.
+    Bech32m,
 }
 
 /// Prints [`WitnessVersion`] number (from 0 to 16) as integer, without any prefix or suffix.
@@ -11,6 +11,8 @@
 //! This module provides the structures and functions needed to support transactions.
 
 use core::{cmp, fmt, str};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
 #[cfg(feature = "arbitrary")]
 use arbitrary::{Arbitrary, Unstructured};
@@ -92,6 +94,13 @@ impl Sealed for super::Wtxid {}
 /// ### Bitcoin Core References
 ///
 /// * [COutPoint definition](https://github.com/bitcoin/bitcoin/blob/345457b542b6a980ccfbc868af0970a6f91d1b82/src/primitives/transaction.h#L26)
+///
+/// ### Ordering and hashing
+///
+/// `OutPoint`s are ordered by `txid` and then by `vout`, the `txid` being compared by its
+/// underlying byte array (i.e. not in the reversed order used by its hex display). This ordering
+/// and the `Hash` implementation are considered stable, making `OutPoint` suitable as a key in
+/// ordered and hashed maps alike, e.g. a UTXO cache.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct OutPoint {
     /// The referenced transaction's txid.
@@ -106,6 +115,12 @@ impl OutPoint {
     /// The number of bytes that an outpoint contributes to the size of a transaction.
     const SIZE: usize = 32 + 4; // The serialized lengths of txid and vout.
 
+    /// The "null" `OutPoint`, used as the previous output of the single input of a coinbase
+    /// transaction.
+    ///
+    /// This is the same value returned by [`OutPoint::null`] but usable in `const` contexts.
+    pub const NULL: Self = OutPoint { txid: Txid::from_byte_array([0; 32]), vout: u32::MAX };
+
     /// Creates a new [`OutPoint`].
     #[inline]
     pub const fn new(txid: Txid, vout: u32) -> OutPoint { OutPoint { txid, vout } }
@@ -114,7 +129,7 @@ pub const fn new(txid: Txid, vout: u32) -> OutPoint { OutPoint { txid, vout } }
     ///
     /// This value is used for coinbase transactions because they don't have any previous outputs.
     #[inline]
-    pub fn null() -> OutPoint { OutPoint { txid: Txid::all_zeros(), vout: u32::MAX } }
+    pub fn null() -> OutPoint { OutPoint::NULL }
 
     /// Checks if an `OutPoint` is "null".
     ///
@@ -131,11 +146,11 @@ pub fn null() -> OutPoint { OutPoint { txid: Txid::all_zeros(), vout: u32::MAX }
     /// assert!(tx.input[0].previous_output.is_null());
     /// ```
     #[inline]
-    pub fn is_null(&self) -> bool { *self == OutPoint::null() }
+    pub fn is_null(&self) -> bool { *self == OutPoint::NULL }
 }
 
 impl Default for OutPoint {
-    fn default() -> Self { OutPoint::null() }
+    fn default() -> Self { OutPoint::NULL }
 }
 
 impl fmt::Display for OutPoint {
@@ -225,6 +240,15 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
     }
 }
 
+/// Sorts `outpoints` and removes any duplicates.
+///
+/// The resulting order is the stable ordering of [`OutPoint`], so the result is deterministic
+/// regardless of the order the outpoints were collected in.
+pub fn dedup_outpoints(outpoints: &mut Vec<OutPoint>) {
+    outpoints.sort_unstable();
+    outpoints.dedup();
+}
+
 /// Bitcoin transaction input.
 ///
 /// It contains the location of the previous transaction's output,
@@ -265,10 +289,11 @@ impl TxIn {
     /// Returns true if this input enables the [`absolute::LockTime`] (aka `nLockTime`) of its
     /// [`Transaction`].
     ///
-    /// `nLockTime` is enabled if *any* input enables it. See [`Transaction::is_lock_time_enabled`]
-    ///  to check the overall state. If none of the inputs enables it, the lock time value is simply
-    ///  ignored. If this returns false and OP_CHECKLOCKTIMEVERIFY is used in the redeem script with
-    ///  this input then the script execution will fail [BIP-0065].
+    /// `nLockTime` is enabled if it is non-zero and *any* input enables it. See
+    /// [`Transaction::is_lock_time_enabled`] to check the overall state. If none of the inputs
+    /// enables it, the lock time value is simply ignored. If this returns false and
+    /// OP_CHECKLOCKTIMEVERIFY is used in the redeem script with this input then the script
+    /// execution will fail [BIP-0065].
     ///
     /// [BIP-65](https://github.com/bitcoin/bips/blob/master/bip-0065.mediawiki)
     pub fn enables_lock_time(&self) -> bool { self.sequence != Sequence::MAX }
@@ -355,6 +380,16 @@ impl TxOut {
     /// This is used as a "null txout" in consensus signing code.
     pub const NULL: Self =
         TxOut { value: Amount::from_sat(0xffffffffffffffff), script_pubkey: ScriptBuf::new() };
+
+    /// Returns the total value of `outputs`.
+    ///
+    /// Returns `None` if the sum overflows. The sum is not checked against [`Amount::MAX_MONEY`].
+    pub fn total_value<'a, I>(outputs: I) -> Option<Amount>
+    where
+        I: IntoIterator<Item = &'a TxOut>,
+    {
+        outputs.into_iter().try_fold(Amount::ZERO, |sum, output| sum.checked_add(output.value))
+    }
 }
 
 crate::internal_macros::define_extension_trait! {
@@ -515,6 +550,11 @@ impl Transaction {
     /// Maximum transaction weight for Bitcoin Core 25.0.
     pub const MAX_STANDARD_WEIGHT: Weight = Weight::from_wu(400_000);
 
+    /// The version used for newly created transactions, the one that enables [BIP-68].
+    ///
+    /// [BIP-68]: https://github.com/bitcoin/bips/blob/master/bip-0068.mediawiki
+    pub const CURRENT_VERSION: Version = Version::TWO;
+
     /// Computes a "normalized TXID" which does not include any signatures.
     ///
     /// This method is deprecated.  Use `compute_ntxid` instead.
@@ -592,6 +632,19 @@ pub fn compute_wtxid(&self) -> Wtxid {
         Wtxid(sha256d::Hash::from_engine(enc))
     }
 
+    /// Returns a copy of this transaction with the witnesses of all inputs cleared.
+    ///
+    /// The copy is serialized without segwit data, which is the pre-segwit serialization that is
+    /// hashed for the [`Txid`]. Its wtxid therefore equals the txid of `self`.
+    #[doc(alias = "strip_witnesses")]
+    pub fn without_witness(&self) -> Transaction {
+        let mut tx = self.clone();
+        for input in &mut tx.input {
+            input.witness.clear();
+        }
+        tx
+    }
+
     /// Returns the weight of this transaction, as defined by BIP-141.
     ///
     /// > Transaction weight is defined as Base transaction size * 3 + Total transaction size (ie.
@@ -611,10 +664,17 @@ pub fn compute_wtxid(&self) -> Wtxid {
     /// API. The unsigned transaction encoded within PSBT is always a non-segwit transaction
     /// and can therefore avoid this ambiguity.
     #[inline]
-    pub fn weight(&self) -> Weight {
-        // This is the exact definition of a weight unit, as defined by BIP-141 (quote above).
-        let wu = self.base_size() * 3 + self.total_size();
-        Weight::from_wu_usize(wu)
+    pub fn weight(&self) -> Weight { Self::weight_from_sizes(self.base_size(), self.total_size()) }
+
+    /// Returns the weight of a transaction with the given base and total sizes.
+    ///
+    /// This is the computation done by [`Transaction::weight`], for callers that already know the
+    /// sizes (as returned by [`Transaction::base_size`] and [`Transaction::total_size`]) and want
+    /// to avoid walking the transaction again.
+    #[inline]
+    pub const fn weight_from_sizes(base_size: usize, total_size: usize) -> Weight {
+        // This is the exact definition of a weight unit, as defined by BIP-141.
+        Weight::from_wu_usize(base_size * 3 + total_size)
     }
 
     /// Returns the base transaction size.
@@ -636,7 +696,11 @@ pub fn base_size(&self) -> usize {
     ///
     /// > Total transaction size is the transaction size in bytes serialized as described in BIP144,
     /// > including base data and witness data.
+    ///
+    /// This is the length of the consensus encoding of the transaction, computed from the field
+    /// sizes without serializing it, e.g. to preallocate a buffer.
     #[inline]
+    #[doc(alias = "encoded_len")]
     pub fn total_size(&self) -> usize {
         let mut size: usize = 4; // Serialized length of a u32 for the version number.
         let uses_segwit = self.uses_segwit_serialization();
@@ -715,8 +779,24 @@ pub fn is_absolute_timelock_satisfied(&self, height: Height, time: Time) -> bool
 
     /// Returns `true` if this transactions nLockTime is enabled ([BIP-65]).
     ///
+    /// The lock time is only enforced if it is non-zero and at least one input has a sequence
+    /// number other than [`Sequence::MAX`] (see [`TxIn::enables_lock_time`]), as in Bitcoin Core's
+    /// `IsFinalTx`.
+    ///
     /// [BIP-65]: https://github.com/bitcoin/bips/blob/master/bip-0065.mediawiki
-    pub fn is_lock_time_enabled(&self) -> bool { self.input.iter().any(|i| i.enables_lock_time()) }
+    pub fn is_lock_time_enabled(&self) -> bool {
+        self.lock_time != absolute::LockTime::ZERO
+            && self.input.iter().any(|i| i.enables_lock_time())
+    }
+
+    /// Returns true if the input sequence numbers of this transaction can encode [BIP-68] relative
+    /// lock times, i.e. if its version is 2 or greater.
+    ///
+    /// Like Bitcoin Core this compares the version as an unsigned integer, so negative versions
+    /// count as greater than 2.
+    ///
+    /// [BIP-68]: https://github.com/bitcoin/bips/blob/master/bip-0068.mediawiki
+    pub fn is_bip68_applicable(&self) -> bool { self.version.0 as u32 >= 2 }
 
     /// Returns an iterator over lengths of `script_pubkey`s in the outputs.
     ///
@@ -868,6 +948,147 @@ pub fn tx_out(&self, output_index: usize) -> Result<&TxOut, OutputsIndexError> {
             .get(output_index)
             .ok_or(IndexOutOfBoundsError { index: output_index, length: self.output.len() }.into())
     }
+
+    /// Sets the value of the output at `output_index`.
+    ///
+    /// Note that this changes the txid and invalidates any existing signatures that commit to the
+    /// output.
+    ///
+    /// # Errors
+    ///
+    /// If `output_index` is out of bounds, in which case the transaction is left unchanged.
+    pub fn set_output_value(
+        &mut self,
+        output_index: usize,
+        value: Amount,
+    ) -> Result<(), OutputsIndexError> {
+        self.tx_out_mut(output_index)?.value = value;
+        Ok(())
+    }
+
+    /// Sets the `script_pubkey` of the output at `output_index`.
+    ///
+    /// Note that this changes the txid and invalidates any existing signatures that commit to the
+    /// output.
+    ///
+    /// # Errors
+    ///
+    /// If `output_index` is out of bounds, in which case the transaction is left unchanged.
+    pub fn set_output_script(
+        &mut self,
+        output_index: usize,
+        script_pubkey: ScriptBuf,
+    ) -> Result<(), OutputsIndexError> {
+        self.tx_out_mut(output_index)?.script_pubkey = script_pubkey;
+        Ok(())
+    }
+
+    fn tx_out_mut(&mut self, output_index: usize) -> Result<&mut TxOut, OutputsIndexError> {
+        let length = self.output.len();
+        self.output
+            .get_mut(output_index)
+            .ok_or(IndexOutOfBoundsError { index: output_index, length }.into())
+    }
+
+    /// Returns an iterator over the inputs paired with the outputs they spend.
+    ///
+    /// The spent output of each input is found by calling `lookup` with its `previous_output`,
+    /// inputs whose prevout is unknown are yielded with `None`. This is the shape needed for
+    /// sighash computation and fee accounting.
+    pub fn inputs_with_prevouts<'a, F>(
+        &'a self,
+        mut lookup: F,
+    ) -> impl Iterator<Item = (&'a TxIn, Option<&'a TxOut>)> + 'a
+    where
+        F: FnMut(&OutPoint) -> Option<&'a TxOut> + 'a,
+    {
+        self.input.iter().map(move |input| (input, lookup(&input.previous_output)))
+    }
+
+    /// Returns the total value of the outputs spent by this transaction.
+    ///
+    /// The spent outputs are looked up in `prevouts` by the `previous_output` of each input.
+    ///
+    /// # Errors
+    ///
+    /// If a spent output is missing from `prevouts` or if the sum overflows.
+    #[cfg(feature = "std")]
+    pub fn input_value(&self, prevouts: &HashMap<OutPoint, TxOut>) -> Result<Amount, FeeError> {
+        self.input.iter().try_fold(Amount::ZERO, |sum, input| {
+            let prevout = prevouts
+                .get(&input.previous_output)
+                .ok_or(FeeError::MissingPrevout(input.previous_output))?;
+            sum.checked_add(prevout.value).ok_or(FeeError::Overflow)
+        })
+    }
+
+    /// Returns the total value of the outputs of this transaction.
+    ///
+    /// Returns `None` if the sum overflows.
+    pub fn output_value(&self) -> Option<Amount> { TxOut::total_value(&self.output) }
+
+    /// Returns the fee rate of this transaction if it pays `fee`.
+    ///
+    /// The rate is computed against [`Transaction::vsize`], as Bitcoin Core does. Because the
+    /// virtual size is rounded up this may be slightly lower than `fee / self.weight()`.
+    pub fn feerate(&self, fee: Amount) -> FeeRate {
+        // 1 sat/vb == 250 sat/kwu
+        let vsize = self.vsize().to_u64();
+        FeeRate::from_sat_per_kwu(fee.to_sat().saturating_mul(250) / vsize)
+    }
+
+    /// Returns the fee paid by this transaction, the value it spends minus the value it creates.
+    ///
+    /// Coinbase transactions do not spend any outputs and therefore have no fee, calling this on
+    /// a coinbase transaction returns an error unless `prevouts` contains the null outpoint.
+    ///
+    /// # Errors
+    ///
+    /// If a spent output is missing from `prevouts`, if the transaction spends less than it
+    /// creates, or if any of the sums overflow.
+    #[cfg(feature = "std")]
+    pub fn fee(&self, prevouts: &HashMap<OutPoint, TxOut>) -> Result<Amount, FeeError> {
+        let input_value = self.input_value(prevouts)?;
+        let output_value = self.output_value().ok_or(FeeError::Overflow)?;
+        input_value.checked_sub(output_value).ok_or(FeeError::NegativeFee)
+    }
+}
+
+/// An error computing the fee of a transaction, see [`Transaction::fee`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FeeError {
+    /// The output spent by a transaction input was not provided.
+    MissingPrevout(OutPoint),
+    /// The transaction creates more value than it spends.
+    NegativeFee,
+    /// An amount sum overflowed.
+    Overflow,
+}
+
+internals::impl_from_infallible!(FeeError);
+
+impl fmt::Display for FeeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use FeeError::*;
+
+        match *self {
+            MissingPrevout(ref op) => write!(f, "missing spent output {}", op),
+            NegativeFee => f.write_str("transaction spends less than it creates"),
+            Overflow => f.write_str("amount overflow computing transaction fee"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FeeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use FeeError::*;
+
+        match *self {
+            MissingPrevout(_) | NegativeFee | Overflow => None,
+        }
+    }
 }
 
 /// Error attempting to do an out of bounds access on the transaction inputs vector.
@@ -1087,6 +1308,114 @@ impl From<&Transaction> for Wtxid {
     fn from(tx: &Transaction) -> Wtxid { tx.compute_wtxid() }
 }
 
+/// A builder for unsigned [`Transaction`]s.
+///
+/// The builder takes care of the interaction between nLockTime and nSequence (BIP-68, BIP-125):
+///
+/// * The transaction version defaults to [`Version::TWO`].
+/// * If a lock time is set, inputs added without an explicit sequence get
+///   [`Sequence::ENABLE_RBF_NO_LOCKTIME`], which enables the lock time and signals replaceability.
+/// * Otherwise inputs default to [`Sequence::MAX`] and the lock time is zero.
+///
+/// All inputs are created with an empty `script_sig` and witness, ready to be signed.
+///
+/// # Examples
+///
+/// ```
+/// use bitcoin::locktime::absolute;
+/// use bitcoin::transaction::TransactionBuilder;
+/// use bitcoin::{Amount, OutPoint, ScriptBuf, Sequence};
+///
+/// let lock_time = absolute::LockTime::from_height(840_000).expect("valid height");
+/// let tx = TransactionBuilder::new()
+///     .input(OutPoint::NULL)
+///     .output(Amount::from_sat(10_000), ScriptBuf::new())
+///     .lock_time(lock_time)
+///     .build();
+///
+/// assert_eq!(tx.lock_time, lock_time);
+/// assert_eq!(tx.input[0].sequence, Sequence::ENABLE_RBF_NO_LOCKTIME);
+/// assert!(tx.is_lock_time_enabled());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionBuilder {
+    version: Version,
+    lock_time: Option<absolute::LockTime>,
+    input: Vec<(OutPoint, Option<Sequence>)>,
+    output: Vec<TxOut>,
+}
+
+impl TransactionBuilder {
+    /// Creates a new builder for a version 2 transaction with no inputs or outputs.
+    pub const fn new() -> Self {
+        TransactionBuilder {
+            version: Version::TWO,
+            lock_time: None,
+            input: Vec::new(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Sets the transaction version.
+    pub fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the transaction lock time.
+    pub fn lock_time(mut self, lock_time: absolute::LockTime) -> Self {
+        self.lock_time = Some(lock_time);
+        self
+    }
+
+    /// Adds an input spending `previous_output` with the default sequence number.
+    pub fn input(mut self, previous_output: OutPoint) -> Self {
+        self.input.push((previous_output, None));
+        self
+    }
+
+    /// Adds an input spending `previous_output` with an explicit sequence number.
+    pub fn input_with_sequence(mut self, previous_output: OutPoint, sequence: Sequence) -> Self {
+        self.input.push((previous_output, Some(sequence)));
+        self
+    }
+
+    /// Adds an output paying `value` to `script_pubkey`.
+    pub fn output(mut self, value: Amount, script_pubkey: ScriptBuf) -> Self {
+        self.output.push(TxOut { value, script_pubkey });
+        self
+    }
+
+    /// Builds the unsigned transaction.
+    pub fn build(self) -> Transaction {
+        let default_sequence = match self.lock_time {
+            Some(_) => Sequence::ENABLE_RBF_NO_LOCKTIME,
+            None => Sequence::MAX,
+        };
+        let input = self
+            .input
+            .into_iter()
+            .map(|(previous_output, sequence)| TxIn {
+                previous_output,
+                script_sig: ScriptBuf::new(),
+                sequence: sequence.unwrap_or(default_sequence),
+                witness: Witness::default(),
+            })
+            .collect();
+
+        Transaction {
+            version: self.version,
+            lock_time: self.lock_time.unwrap_or(absolute::LockTime::ZERO),
+            input,
+            output: self.output,
+        }
+    }
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self { Self::new() }
+}
+
 /// Computes the value of an output accounting for the cost of spending it.
 ///
 /// The effective value is the value of an output value minus the amount to spend it.  That is, the
@@ -1416,6 +1745,52 @@ pub const fn weight(&self) -> Weight {
     }
 }
 
+/// Fixed values for use in tests.
+///
+/// These are valid, deterministic and made-up: they do not refer to anything on any chain.
+#[cfg(feature = "test-utils")]
+mod dummy {
+    use super::*;
+    use crate::address::script_pubkey::ScriptBufExt as _;
+    use crate::WPubkeyHash;
+
+    impl OutPoint {
+        /// Returns an outpoint spending output `index` of a made-up, non-null transaction.
+        pub fn dummy(index: u32) -> Self {
+            OutPoint { txid: Txid::from_byte_array([0x01; 32]), vout: index }
+        }
+    }
+
+    impl TxOut {
+        /// Returns a 1 BTC output paying to a made-up P2WPKH script.
+        pub fn dummy() -> Self {
+            TxOut {
+                value: Amount::ONE_BTC,
+                script_pubkey: ScriptBuf::new_p2wpkh(WPubkeyHash::from_byte_array([0x02; 20])),
+            }
+        }
+    }
+
+    impl Transaction {
+        /// Returns a version 2 transaction spending [`OutPoint::dummy`] to [`TxOut::dummy`].
+        ///
+        /// The input has an empty script sig and witness, the lock time is zero.
+        pub fn dummy() -> Self {
+            Transaction {
+                version: Version::TWO,
+                lock_time: absolute::LockTime::ZERO,
+                input: vec![TxIn {
+                    previous_output: OutPoint::dummy(0),
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::MAX,
+                    witness: Witness::new(),
+                }],
+                output: vec![TxOut::dummy()],
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use hex::{test_hex_unwrap as hex, FromHex};
@@ -1524,6 +1899,192 @@ fn txin_default() {
         assert_eq!(txin.witness.len(), 0);
     }
 
+    #[test]
+    fn transaction_builder_defaults() {
+        let script_pubkey =
+            ScriptBuf::from_bytes(hex!("76a9140389035a9225b3839e2bbf32d826a1e222031fd888ac"));
+        let tx = TransactionBuilder::new()
+            .input(OutPoint::NULL)
+            .output(Amount::from_sat(1_000), script_pubkey.clone())
+            .build();
+
+        assert_eq!(tx.version, Version::TWO);
+        assert_eq!(tx.lock_time, absolute::LockTime::ZERO);
+        assert_eq!(tx.input.len(), 1);
+        assert_eq!(tx.input[0].sequence, Sequence::MAX);
+        assert!(tx.input[0].script_sig.is_empty());
+        assert!(tx.input[0].witness.is_empty());
+        assert_eq!(tx.output, vec![TxOut { value: Amount::from_sat(1_000), script_pubkey }]);
+        assert!(!tx.is_lock_time_enabled());
+        assert!(!tx.is_explicitly_rbf());
+    }
+
+    #[test]
+    fn is_lock_time_enabled() {
+        use crate::constants::genesis_block;
+        use crate::network::Network;
+
+        let block = genesis_block(Network::Bitcoin);
+        let mut tx = block.txdata[0].clone();
+        assert_eq!(tx.lock_time, absolute::LockTime::ZERO);
+        assert!(!tx.is_lock_time_enabled());
+
+        // A zero lock time is never enforced, whatever the sequence numbers.
+        tx.input[0].sequence = Sequence::ENABLE_RBF_NO_LOCKTIME;
+        assert!(!tx.is_lock_time_enabled());
+
+        tx.lock_time = absolute::LockTime::from_consensus(1);
+        assert!(tx.is_lock_time_enabled());
+
+        tx.input[0].sequence = Sequence::MAX;
+        assert!(!tx.is_lock_time_enabled());
+    }
+
+    #[test]
+    fn inputs_with_prevouts() {
+        let known = OutPoint { txid: Txid::from_byte_array([0x01; 32]), vout: 0 };
+        let unknown = OutPoint { txid: Txid::from_byte_array([0x01; 32]), vout: 1 };
+        let tx = TransactionBuilder::new().input(known).input(unknown).build();
+
+        let spent = TxOut { value: Amount::ONE_BTC, script_pubkey: ScriptBuf::new() };
+        let prevouts = [(known, spent.clone())];
+        let lookup = |outpoint: &OutPoint| {
+            prevouts.iter().find(|(op, _)| op == outpoint).map(|(_, txout)| txout)
+        };
+
+        let mut pairs = tx.inputs_with_prevouts(lookup);
+        assert_eq!(pairs.next(), Some((&tx.input[0], Some(&spent))));
+        assert_eq!(pairs.next(), Some((&tx.input[1], None)));
+        assert_eq!(pairs.next(), None);
+    }
+
+    #[test]
+    fn transaction_builder_lock_time_enables_rbf() {
+        let lock_time = absolute::LockTime::from_height(800_000).unwrap();
+        let tx = TransactionBuilder::new()
+            .version(Version::ONE)
+            .input(OutPoint::NULL)
+            .input_with_sequence(OutPoint::NULL, Sequence::MAX)
+            .lock_time(lock_time)
+            .build();
+
+        assert_eq!(tx.version, Version::ONE);
+        assert_eq!(tx.lock_time, lock_time);
+        assert_eq!(tx.input[0].sequence, Sequence::ENABLE_RBF_NO_LOCKTIME);
+        assert_eq!(tx.input[1].sequence, Sequence::MAX);
+        assert!(tx.is_lock_time_enabled());
+        assert!(tx.is_explicitly_rbf());
+        assert_eq!(TransactionBuilder::default(), TransactionBuilder::new());
+    }
+
+    #[test]
+    fn txout_total_value() {
+        let output = |sat| TxOut { value: Amount::from_sat(sat), script_pubkey: ScriptBuf::new() };
+
+        assert_eq!(TxOut::total_value(&[] as &[TxOut]), Some(Amount::ZERO));
+        let outputs = [output(1_000), output(2_000), output(3_000)];
+        assert_eq!(TxOut::total_value(&outputs), Some(Amount::from_sat(6_000)));
+
+        // Summing over the outputs of several transactions.
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![],
+            output: outputs.to_vec(),
+        };
+        let txs = [tx.clone(), tx];
+        let total = TxOut::total_value(txs.iter().flat_map(|tx| &tx.output));
+        assert_eq!(total, Some(Amount::from_sat(12_000)));
+        assert_eq!(txs[0].output_value(), Some(Amount::from_sat(6_000)));
+
+        // Not capped at `MAX_MONEY`, only overflow is an error.
+        let outputs = [output(Amount::MAX_MONEY.to_sat()), output(1)];
+        assert_eq!(TxOut::total_value(&outputs), Some(Amount::MAX_MONEY + Amount::ONE_SAT));
+        let outputs = [output(u64::MAX), output(1)];
+        assert_eq!(TxOut::total_value(&outputs), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn transaction_fee() {
+        let tx: Transaction = deserialize(&hex!(SOME_TX)).unwrap();
+        let output_value = tx.output_value().unwrap();
+        let prevout = tx.input[0].previous_output;
+
+        let spent = TxOut {
+            value: output_value + Amount::from_sat(1_000),
+            script_pubkey: ScriptBuf::new(),
+        };
+        let mut prevouts = HashMap::new();
+        prevouts.insert(prevout, spent.clone());
+        assert_eq!(tx.input_value(&prevouts), Ok(spent.value));
+        assert_eq!(tx.fee(&prevouts), Ok(Amount::from_sat(1_000)));
+
+        let exact = TxOut { value: output_value, script_pubkey: ScriptBuf::new() };
+        prevouts.insert(prevout, exact);
+        assert_eq!(tx.fee(&prevouts), Ok(Amount::ZERO));
+
+        let short =
+            TxOut { value: output_value - Amount::from_sat(1), script_pubkey: ScriptBuf::new() };
+        prevouts.insert(prevout, short);
+        assert_eq!(tx.fee(&prevouts), Err(FeeError::NegativeFee));
+
+        assert_eq!(tx.fee(&HashMap::new()), Err(FeeError::MissingPrevout(prevout)));
+    }
+
+    #[test]
+    fn transaction_feerate() {
+        // Legacy transaction, the virtual size is the serialized size.
+        let tx: Transaction = deserialize(&hex!(SOME_TX)).unwrap();
+        assert_eq!(tx.vsize(), 193);
+        let rate = tx.feerate(Amount::from_sat(1930));
+        assert_eq!(rate, FeeRate::from_sat_per_vb_unchecked(10));
+        assert_eq!(rate.fee_vb(tx.vsize().to_u64()), Some(Amount::from_sat(1930)));
+
+        // Segwit transaction with a weight of 442 wu, rounded up to a virtual size of 111 vbytes.
+        let tx: Transaction = deserialize(&hex!(
+            "02000000000101595895ea20179de87052b4046dfe6fd515860505d6511a9004cf12a1f93cac7c01000000\
+            00ffffffff01deb807000000000017a9140f3444e271620c736808aa7b33e370bd87cb5a078702483045022\
+            100fb60dad8df4af2841adc0346638c16d0b8035f5e3f3753b88db122e70c79f9370220756e6633b17fd271\
+            0e626347d28d60b0a2d6cbb41de51740644b9fb3ba7751040121028fa937ca8cba2197a37c007176ed89410\
+            55d3bcb8627d085e94553e62f057dcc00000000"
+        ))
+        .unwrap();
+        assert_eq!(tx.vsize(), 111);
+        let fee = Amount::from_sat(1110);
+        assert_eq!(tx.feerate(fee), FeeRate::from_sat_per_vb_unchecked(10));
+        assert_eq!((fee / tx.weight()).to_sat_per_kwu(), 2511);
+
+        assert_eq!(tx.feerate(Amount::ZERO), FeeRate::ZERO);
+    }
+
+    #[test]
+    fn outpoint_dedup() {
+        let txid_a = Txid::from_byte_array([1; 32]);
+        let txid_b = Txid::from_byte_array([2; 32]);
+        let mut outpoints = vec![
+            OutPoint::new(txid_b, 0),
+            OutPoint::new(txid_a, 7),
+            OutPoint::new(txid_b, 0),
+            OutPoint::new(txid_a, 1),
+            OutPoint::new(txid_a, 7),
+        ];
+        dedup_outpoints(&mut outpoints);
+
+        assert_eq!(
+            outpoints,
+            vec![OutPoint::new(txid_a, 1), OutPoint::new(txid_a, 7), OutPoint::new(txid_b, 0)]
+        );
+    }
+
+    #[test]
+    fn outpoint_null_const() {
+        assert_eq!(OutPoint::NULL, OutPoint::null());
+        assert_eq!(OutPoint::NULL, OutPoint::default());
+        assert_eq!(OutPoint::NULL.txid, Txid::all_zeros());
+        assert!(OutPoint::NULL.is_null());
+    }
+
     #[test]
     fn is_coinbase() {
         use crate::constants;
@@ -1531,6 +2092,7 @@ fn is_coinbase() {
 
         let genesis = constants::genesis_block(Network::Bitcoin);
         assert!(genesis.txdata[0].is_coinbase());
+        assert_eq!(genesis.txdata[0].input[0].previous_output, OutPoint::NULL);
         let tx_bytes = hex!("0100000001a15d57094aa7a21a28cb20b59aab8fc7d1149a3bdbcddba9c622e4f5f6a99ece010000006c493046022100f93bb0e7d8db7bd46e40132d1f8242026e045f03a0efe71bbb8e3f475e970d790221009337cd7f1f929f00cc6ff01f03729b069a7c21b59b1736ddfee5db5946c5da8c0121033b9b137ee87d5a812d6f506efdd37f0affa7ffc310711c06c7f3e097c9447c52ffffffff0100e1f505000000001976a9140389035a9225b3839e2bbf32d826a1e222031fd888ac00000000");
         let tx: Transaction = deserialize(&tx_bytes).unwrap();
         assert!(!tx.is_coinbase());
@@ -1768,6 +2330,34 @@ fn txn_encode_decode() {
         serde_round_trip!(tx);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn genesis_tx_serde_json() {
+        use crate::constants::genesis_block;
+        use crate::network::Network;
+
+        let tx = genesis_block(Network::Bitcoin).txdata[0].clone();
+        let json = serde_json::to_value(&tx).unwrap();
+
+        // Outpoints use the display-order txid, amounts are in satoshis and scripts are hex.
+        assert_eq!(
+            json["input"][0]["previous_output"],
+            "0000000000000000000000000000000000000000000000000000000000000000:4294967295"
+        );
+        assert_eq!(json["output"][0]["value"], 50 * 100_000_000_u64);
+        assert_eq!(
+            json["output"][0]["script_pubkey"],
+            "4104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac"
+        );
+
+        let decoded: Transaction = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(
+            decoded.compute_txid().to_string(),
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+        );
+    }
+
     // Test decoding transaction `4be105f158ea44aec57bf12c5817d073a712ab131df6f37786872cfc70734188`
     // from testnet, which is the first BIP144-encoded transaction I encountered.
     #[test]
@@ -1891,6 +2481,18 @@ fn transaction_verify() {
         }
     }
 
+    #[test]
+    #[cfg(feature = "bitcoinconsensus")]
+    fn transaction_verify_unknown_spent_output() {
+        use crate::consensus_validation::TxVerifyError;
+
+        let tx: Transaction = deserialize(&hex!(SOME_TX)).unwrap();
+        let prevout = tx.input[0].previous_output;
+
+        let error = tx.verify(|_: &OutPoint| None).unwrap_err();
+        assert_eq!(error, TxVerifyError::UnknownSpentOutput(prevout));
+    }
+
     #[test]
     fn sequence_number() {
         let seq_final = Sequence::from_consensus(0xFFFFFFFF);
@@ -1912,6 +2514,21 @@ fn sequence_number() {
         assert!(!lock_time_disabled.is_relative_lock_time());
     }
 
+    #[test]
+    fn sequence_consensus_round_trip() {
+        const SEQ: Sequence = Sequence::from_consensus(0x1234_5678);
+        const N: u32 = SEQ.to_consensus_u32();
+        assert_eq!(N, 0x1234_5678);
+
+        for n in [0, 0xFFFF, 0x0040_FFFF, 0x8000_0000, 0xFFFF_FFFE, 0xFFFF_FFFF] {
+            let seq = Sequence::from_consensus(n);
+            assert_eq!(seq.to_consensus_u32(), n);
+            assert_eq!(serialize(&seq), n.to_le_bytes());
+            assert_eq!(deserialize::<Sequence>(&n.to_le_bytes()).unwrap(), seq);
+        }
+        assert_eq!(Sequence::MAX.to_consensus_u32(), u32::MAX);
+    }
+
     #[test]
     fn sequence_from_hex_lower() {
         let sequence = Sequence::from_hex("0xffffffff").unwrap();
@@ -2019,6 +2636,167 @@ fn txin_txout_weight() {
         }
     }
 
+    #[test]
+    fn weight_scales_base_size() {
+        // [(is_segwit, tx_hex)], the vectors of `txin_txout_weight`.
+        let txs = [
+                // one segwit input (P2WPKH)
+                (true, "020000000001018a763b78d3e17acea0625bf9e52b0dc1beb2241b2502185348ba8ff4a253176e0100000000ffffffff0280d725000000000017a914c07ed639bd46bf7087f2ae1dfde63b815a5f8b488767fda20300000000160014869ec8520fa2801c8a01bfdd2e82b19833cd0daf02473044022016243edad96b18c78b545325aaff80131689f681079fb107a67018cb7fb7830e02205520dae761d89728f73f1a7182157f6b5aecf653525855adb7ccb998c8e6143b012103b9489bde92afbcfa85129a82ffa512897105d1a27ad9806bded27e0532fc84e700000000"),
+                // one segwit input (P2WSH)
+                (true, "01000000000101a3ccad197118a2d4975fadc47b90eacfdeaf8268adfdf10ed3b4c3b7e1ad14530300000000ffffffff0200cc5501000000001976a91428ec6f21f4727bff84bb844e9697366feeb69f4d88aca2a5100d00000000220020701a8d401c84fb13e6baf169d59684e17abd9fa216c8cc5b9fc63d622ff8c58d04004730440220548f11130353b3a8f943d2f14260345fc7c20bde91704c9f1cbb5456355078cd0220383ed4ed39b079b618bcb279bbc1f2ca18cb028c4641cb522c9c5868c52a0dc20147304402203c332ecccb3181ca82c0600520ee51fee80d3b4a6ab110945e59475ec71e44ac0220679a11f3ca9993b04ccebda3c834876f353b065bb08f50076b25f5bb93c72ae1016952210375e00eb72e29da82b89367947f29ef34afb75e8654f6ea368e0acdfd92976b7c2103a1b26313f430c4b15bb1fdce663207659d8cac749a0e53d70eff01874496feff2103c96d495bfdd5ba4145e3e046fee45e84a8a48ad05bd8dbb395c011a32cf9f88053ae00000000"),
+                // one segwit input (P2WPKH) and two legacy inputs (P2PKH)
+                (true, "010000000001036b6b6ac7e34e97c53c1cc74c99c7948af2e6aac75d8778004ae458d813456764000000006a473044022001deec7d9075109306320b3754188f81a8236d0d232b44bc69f8309115638b8f02204e17a5194a519cf994d0afeea1268740bdc10616b031a521113681cc415e815c012103488d3272a9fad78ee887f0684cb8ebcfc06d0945e1401d002e590c7338b163feffffffffc75bd7aa6424aee972789ec28ba181254ee6d8311b058d165bd045154d7660b0000000006b483045022100c8641bcbee3e4c47a00417875015d8c5d5ea918fb7e96f18c6ffe51bc555b401022074e2c46f5b1109cd79e39a9aa203eadd1d75356415e51d80928a5fb5feb0efee0121033504b4c6dfc3a5daaf7c425aead4c2dbbe4e7387ce8e6be2648805939ecf7054ffffffff494df3b205cd9430a26f8e8c0dc0bb80496fbc555a524d6ea307724bc7e60eee0100000000ffffffff026d861500000000001976a9145c54ed1360072ebaf56e87693b88482d2c6a101588ace407000000000000160014761e31e2629c6e11936f2f9888179d60a5d4c1f900000247304402201fa38a67a63e58b67b6cfffd02f59121ca1c8a1b22e1efe2573ae7e4b4f06c2b022002b9b431b58f6e36b3334fb14eaecee7d2f06967a77ef50d8d5f90dda1057f0c01210257dc6ce3b1100903306f518ee8fa113d778e403f118c080b50ce079fba40e09a00000000"),
+                // three legacy inputs (P2PKH)
+                (false, "0100000003e4d7be4314204a239d8e00691128dca7927e19a7339c7948bde56f669d27d797010000006b483045022100b988a858e2982e2daaf0755b37ad46775d6132057934877a5badc91dee2f66ff022020b967c1a2f0916007662ec609987e951baafa6d4fda23faaad70715611d6a2501210254a2dccd8c8832d4677dc6f0e562eaaa5d11feb9f1de2c50a33832e7c6190796ffffffff9e22eb1b3f24c260187d716a8a6c2a7efb5af14a30a4792a6eeac3643172379c000000006a47304402207df07f0cd30dca2cf7bed7686fa78d8a37fe9c2254dfdca2befed54e06b779790220684417b8ff9f0f6b480546a9e90ecee86a625b3ea1e4ca29b080da6bd6c5f67e01210254a2dccd8c8832d4677dc6f0e562eaaa5d11feb9f1de2c50a33832e7c6190796ffffffff1123df3bfb503b59769731da103d4371bc029f57979ebce68067768b958091a1000000006a47304402207a016023c2b0c4db9a7d4f9232fcec2193c2f119a69125ad5bcedcba56dd525e02206a734b3a321286c896759ac98ebfd9d808df47f1ce1fbfbe949891cc3134294701210254a2dccd8c8832d4677dc6f0e562eaaa5d11feb9f1de2c50a33832e7c6190796ffffffff0200c2eb0b000000001976a914e5eb3e05efad136b1405f5c2f9adb14e15a35bb488ac88cfff1b000000001976a9144846db516db3130b7a3c92253599edec6bc9630b88ac00000000"),
+                // one segwit input (P2TR)
+                (true, "01000000000101b5cee87f1a60915c38bb0bc26aaf2b67be2b890bbc54bb4be1e40272e0d2fe0b0000000000ffffffff025529000000000000225120106daad8a5cb2e6fc74783714273bad554a148ca2d054e7a19250e9935366f3033760000000000002200205e6d83c44f57484fd2ef2a62b6d36cdcd6b3e06b661e33fd65588a28ad0dbe060141df9d1bfce71f90d68bf9e9461910b3716466bfe035c7dbabaa7791383af6c7ef405a3a1f481488a91d33cd90b098d13cb904323a3e215523aceaa04e1bb35cdb0100000000"),
+                // one legacy input (P2PKH)
+                (false, "0100000001c336895d9fa674f8b1e294fd006b1ac8266939161600e04788c515089991b50a030000006a47304402204213769e823984b31dcb7104f2c99279e74249eacd4246dabcf2575f85b365aa02200c3ee89c84344ae326b637101a92448664a8d39a009c8ad5d147c752cbe112970121028b1b44b4903c9103c07d5a23e3c7cf7aeb0ba45ddbd2cfdce469ab197381f195fdffffff040000000000000000536a4c5058325bb7b7251cf9e36cac35d691bd37431eeea426d42cbdecca4db20794f9a4030e6cb5211fabf887642bcad98c9994430facb712da8ae5e12c9ae5ff314127d33665000bb26c0067000bb0bf00322a50c300000000000017a9145ca04fdc0a6d2f4e3f67cfeb97e438bb6287725f8750c30000000000001976a91423086a767de0143523e818d4273ddfe6d9e4bbcc88acc8465003000000001976a914c95cbacc416f757c65c942f9b6b8a20038b9b12988ac00000000"),
+            ];
+
+        for (is_segwit, tx) in &txs {
+            let tx: Transaction = deserialize(Vec::from_hex(tx).unwrap().as_slice()).unwrap();
+
+            // Non-witness bytes count `WITNESS_SCALE_FACTOR` times, witness bytes count once.
+            let (base, total) = (tx.base_size(), tx.total_size());
+            assert_eq!(total, serialize(&tx).len());
+            assert_eq!(*is_segwit, total > base);
+            assert_eq!(tx.weight().to_wu() as usize, base * (WITNESS_SCALE_FACTOR - 1) + total);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "test-utils")]
+    fn dummies() {
+        let tx = Transaction::dummy();
+        assert_eq!(tx, Transaction::dummy());
+        assert!(!tx.is_coinbase());
+        assert!(!tx.uses_segwit_serialization());
+        assert!(tx.output[0].script_pubkey.is_p2wpkh());
+
+        let back: Transaction = deserialize(&serialize(&tx)).unwrap();
+        assert_eq!(back, tx);
+
+        assert!(!OutPoint::dummy(0).is_null());
+        assert_ne!(OutPoint::dummy(0), OutPoint::dummy(1));
+        assert_eq!(OutPoint::dummy(7).vout, 7);
+    }
+
+    #[test]
+    fn bip68_applicable_by_version() {
+        let mut tx: Transaction = deserialize(&hex!(SOME_TX)).unwrap();
+        assert_eq!(tx.version, Version::ONE);
+        assert!(!tx.is_bip68_applicable());
+
+        tx.version = Transaction::CURRENT_VERSION;
+        assert!(tx.is_bip68_applicable());
+        tx.version = Version::non_standard(3);
+        assert!(tx.is_bip68_applicable());
+        tx.version = Version::non_standard(0);
+        assert!(!tx.is_bip68_applicable());
+        // Compared as unsigned like in Core.
+        tx.version = Version::non_standard(-1);
+        assert!(tx.is_bip68_applicable());
+    }
+
+    #[test]
+    fn total_size_is_encoded_len() {
+        use crate::constants::genesis_block;
+        use crate::network::Network;
+
+        let block = genesis_block(Network::Bitcoin);
+        let genesis = &block.txdata[0];
+        assert_eq!(genesis.total_size(), 204);
+        assert_eq!(genesis.total_size(), serialize(genesis).len());
+        assert_eq!(genesis.base_size(), genesis.total_size());
+
+        let tx: Transaction = deserialize(&hex!(SOME_TX)).unwrap();
+        let mut buf = Vec::with_capacity(tx.total_size());
+        tx.consensus_encode(&mut buf).unwrap();
+        assert_eq!(buf.len(), tx.total_size());
+    }
+
+    #[test]
+    fn without_witness() {
+        let tx: Transaction = deserialize(&hex!(
+            "02000000000101595895ea20179de87052b4046dfe6fd515860505d6511a9004cf12a1f93cac7c01000000\
+            00ffffffff01deb807000000000017a9140f3444e271620c736808aa7b33e370bd87cb5a078702483045022\
+            100fb60dad8df4af2841adc0346638c16d0b8035f5e3f3753b88db122e70c79f9370220756e6633b17fd271\
+            0e626347d28d60b0a2d6cbb41de51740644b9fb3ba7751040121028fa937ca8cba2197a37c007176ed89410\
+            55d3bcb8627d085e94553e62f057dcc00000000"
+        ))
+        .unwrap();
+        let stripped = tx.without_witness();
+
+        assert!(stripped.input.iter().all(|input| input.witness.is_empty()));
+        assert_eq!(stripped.compute_txid(), tx.compute_txid());
+        assert_eq!(stripped.compute_wtxid().to_byte_array(), tx.compute_txid().to_byte_array());
+        assert_ne!(tx.compute_wtxid(), stripped.compute_wtxid());
+        assert_eq!(stripped.total_size(), tx.base_size());
+        assert_eq!(serialize(&stripped).len(), tx.base_size());
+
+        // Stripping a transaction without witnesses is a no-op.
+        assert_eq!(stripped.without_witness(), stripped);
+    }
+
+    #[test]
+    fn weight_from_sizes() {
+        let tx: Transaction = deserialize(&hex!(SOME_TX)).unwrap();
+        let (base, total) = (tx.base_size(), tx.total_size());
+        assert_eq!(Transaction::weight_from_sizes(base, total), tx.weight());
+
+        let segwit: Transaction = deserialize(&hex!(
+            "02000000000101595895ea20179de87052b4046dfe6fd515860505d6511a9004cf12a1f93cac7c01000000\
+            00ffffffff01deb807000000000017a9140f3444e271620c736808aa7b33e370bd87cb5a078702483045022\
+            100fb60dad8df4af2841adc0346638c16d0b8035f5e3f3753b88db122e70c79f9370220756e6633b17fd271\
+            0e626347d28d60b0a2d6cbb41de51740644b9fb3ba7751040121028fa937ca8cba2197a37c007176ed89410\
+            55d3bcb8627d085e94553e62f057dcc00000000"
+        ))
+        .unwrap();
+        let (base, total) = (segwit.base_size(), segwit.total_size());
+        assert!(total > base);
+        assert_eq!(Transaction::weight_from_sizes(base, total), Weight::from_wu(442));
+
+        assert_eq!(Transaction::weight_from_sizes(0, 0), Weight::ZERO);
+        assert_eq!(Transaction::weight_from_sizes(10, 10), Weight::from_wu(40));
+        assert_eq!(Transaction::weight_from_sizes(10, 12), Weight::from_wu(42));
+    }
+
+    #[test]
+    fn set_output_value_and_script() {
+        let mut tx: Transaction = deserialize(&hex!(SOME_TX)).unwrap();
+        let before = tx.clone();
+        let script = ScriptBuf::from_bytes(hex!("0014869ec8520fa2801c8a01bfdd2e82b19833cd0daf"));
+
+        tx.set_output_value(0, Amount::from_sat(1_000)).unwrap();
+        tx.set_output_script(0, script.clone()).unwrap();
+        assert_eq!(tx.output[0].value, Amount::from_sat(1_000));
+        assert_eq!(tx.output[0].script_pubkey, script);
+        assert_ne!(tx.compute_txid(), before.compute_txid());
+
+        let mut unchanged = before.clone();
+        let err = unchanged.set_output_value(1, Amount::ZERO).unwrap_err();
+        assert_eq!(err, OutputsIndexError(IndexOutOfBoundsError { index: 1, length: 1 }));
+        assert!(unchanged.set_output_script(usize::MAX, ScriptBuf::new()).is_err());
+        assert_eq!(unchanged, before);
+    }
+
+    #[test]
+    fn weight_output_count_varint_boundary() {
+        let txout = TxOut { value: Amount::ZERO, script_pubkey: ScriptBuf::new() };
+        let tx_with_outputs = |n| Transaction {
+            version: Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn::default()],
+            output: vec![txout.clone(); n],
+        };
+
+        let below = tx_with_outputs(0xfc).weight();
+        let at = tx_with_outputs(0xfd).weight();
+        // One more 9 byte output plus the count growing from 1 to 3 bytes.
+        assert_eq!(at - below, Weight::from_wu((9 + 2) * WITNESS_SCALE_FACTOR as u64));
+    }
+
     #[test]
     fn tx_sigop_count() {
         let tx_hexes = [
@@ -326,6 +326,16 @@ pub fn size(&self) -> usize {
         size
     }
 
+    /// Returns the weight a witness with elements of the given sizes contributes to a transaction.
+    ///
+    /// Witness data is discounted, each byte counts as a single weight unit, so this is the
+    /// serialized size of such a witness. Useful for estimating the weight of an input before it
+    /// is signed, e.g. `&[72, 33]` for a P2WPKH spend with a worst case ECDSA signature.
+    pub fn estimated_weight(elements: &[usize]) -> usize {
+        VarInt::from(elements.len()).size()
+            + elements.iter().map(|&len| VarInt::from(len).size() + len).sum::<usize>()
+    }
+
     /// Clear the witness.
     pub fn clear(&mut self) {
         self.content.clear();
@@ -708,6 +718,19 @@ fn test_iter_len() {
         }
     }
 
+    #[test]
+    fn witness_estimated_weight() {
+        assert_eq!(Witness::estimated_weight(&[]), 1);
+        // P2WPKH: 72 byte signature and 33 byte compressed key.
+        assert_eq!(Witness::estimated_weight(&[72, 33]), 108);
+
+        let elements = [vec![0u8; 72], vec![0u8; 33], vec![], vec![0u8; 300]];
+        let witness = Witness::from_slice(&elements);
+        let lens = elements.iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(Witness::estimated_weight(&lens), witness.size());
+        assert_eq!(Witness::estimated_weight(&lens), serialize(&witness).len());
+    }
+
     #[test]
     fn test_push_ecdsa_sig() {
         // The very first signature in block 734,958
@@ -857,6 +880,27 @@ fn test_tx() {
         assert_eq!(tx_bytes_back, tx_bytes);
     }
 
+    #[test]
+    fn witness_len_and_is_empty() {
+        use crate::constants::genesis_block;
+        use crate::Network;
+
+        let genesis = genesis_block(Network::Bitcoin);
+        let coinbase_witness = &genesis.txdata[0].input[0].witness;
+        assert!(coinbase_witness.is_empty());
+        assert_eq!(coinbase_witness.len(), 0);
+        assert_eq!(coinbase_witness.nth(0), None);
+
+        // A P2WPKH spend has the signature then the public key.
+        let witness = Witness::from_slice(&[hex!("3044022001"), hex!("02aabbcc")]);
+        assert!(!witness.is_empty());
+        assert_eq!(witness.len(), 2);
+        assert_eq!(witness.len(), witness.iter().len());
+        assert_eq!(witness.nth(0), Some(&hex!("3044022001")[..]));
+        assert_eq!(witness.nth(1), witness.last());
+        assert_eq!(witness.nth(2), None);
+    }
+
     #[test]
     fn fuzz_cases() {
         let bytes = hex!("26ff0000000000c94ce592cf7a4cbb68eb00ce374300000057cd0000000000000026");
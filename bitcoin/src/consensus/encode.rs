@@ -436,10 +436,18 @@ fn consensus_encode<W: Write + ?Sized>(
 
 #[allow(clippy::len_without_is_empty)] // VarInt has no concept of 'is_empty'.
 impl VarInt {
+    /// Creates a `VarInt` from a `u64`.
+    ///
+    /// Equivalent to `VarInt(n)`, usable in const context.
+    #[inline]
+    pub const fn from_u64(n: u64) -> Self { VarInt(n) }
+
     /// Returns the number of bytes this varint contributes to a transaction size.
     ///
     /// Returns 1 for 0..=0xFC, 3 for 0xFD..=(2^16-1), 5 for 0x10000..=(2^32-1), and 9 otherwise.
+    /// This is always equal to the number of bytes written by `consensus_encode`.
     #[inline]
+    #[doc(alias = "len")]
     pub const fn size(&self) -> usize {
         match self.0 {
             0..=0xFC => 1,
@@ -981,6 +989,18 @@ fn serialize_varint_test() {
         test_varint_len(VarInt(u64::MAX), 9);
     }
 
+    #[test]
+    fn varint_from_u64() {
+        const MAX: VarInt = VarInt::from_u64(u64::MAX);
+        assert_eq!(MAX, VarInt(u64::MAX));
+
+        for n in [0, 0xFC, 0xFD, 0xFFFF, 0x10000, 0xFFFFFFFF, 0xFFFFFFFF + 1, u64::MAX] {
+            let varint = VarInt::from_u64(n);
+            assert_eq!(varint.0, n);
+            assert_eq!(varint.size(), serialize(&varint).len());
+        }
+    }
+
     fn test_varint_len(varint: VarInt, expected: usize) {
         let mut encoder = vec![];
         assert_eq!(varint.consensus_encode(&mut encoder).unwrap(), expected);
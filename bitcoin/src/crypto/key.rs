@@ -15,6 +15,7 @@
 use internals::{impl_to_hex_from_lower_hex, write_err};
 use io::{Read, Write};
 
+use crate::constants::{SECRET_KEY_PREFIX_MAIN, SECRET_KEY_PREFIX_TEST};
 use crate::crypto::ecdsa;
 use crate::internal_macros::impl_asref_push_bytes;
 use crate::network::NetworkKind;
@@ -457,7 +458,11 @@ pub fn from_slice(
     #[rustfmt::skip]
     pub fn fmt_wif(&self, fmt: &mut dyn fmt::Write) -> fmt::Result {
         let mut ret = [0; 34];
-        ret[0] = if self.network.is_mainnet() { 128 } else { 239 };
+        ret[0] = if self.network.is_mainnet() {
+            SECRET_KEY_PREFIX_MAIN
+        } else {
+            SECRET_KEY_PREFIX_TEST
+        };
 
         ret[1..33].copy_from_slice(&self.inner[..]);
         let privkey = if self.compressed {
@@ -478,6 +483,8 @@ pub fn to_wif(self) -> String {
     }
 
     /// Parses the WIF encoded private key.
+    ///
+    /// The network kind and whether the public key is compressed are recovered from the encoding.
     pub fn from_wif(wif: &str) -> Result<PrivateKey, FromWifError> {
         let data = base58::decode_check(wif)?;
 
@@ -490,8 +497,8 @@ pub fn from_wif(wif: &str) -> Result<PrivateKey, FromWifError> {
         };
 
         let network = match data[0] {
-            128 => NetworkKind::Main,
-            239 => NetworkKind::Test,
+            SECRET_KEY_PREFIX_MAIN => NetworkKind::Main,
+            SECRET_KEY_PREFIX_TEST => NetworkKind::Test,
             invalid => {
                 return Err(InvalidAddressVersionError { invalid }.into());
             }
@@ -1142,6 +1149,41 @@ impl std::error::Error for InvalidAddressVersionError {}
 mod tests {
     use super::*;
     use crate::address::Address;
+    use crate::constants::PUBKEY_ADDRESS_PREFIX_MAIN;
+
+    #[test]
+    fn wif_prefix_and_compression() {
+        let inner = secp256k1::SecretKey::from_slice(&[1; 32]).unwrap();
+        for network in [NetworkKind::Main, NetworkKind::Test] {
+            for compressed in [true, false] {
+                let sk = PrivateKey { compressed, network, inner };
+                let wif = sk.to_wif();
+                assert_eq!(PrivateKey::from_wif(&wif).unwrap(), sk);
+
+                let data = base58::decode_check(&wif).unwrap();
+                let prefix = match network {
+                    NetworkKind::Main => SECRET_KEY_PREFIX_MAIN,
+                    NetworkKind::Test => SECRET_KEY_PREFIX_TEST,
+                };
+                assert_eq!(data[0], prefix);
+                assert_eq!(&data[1..33], &inner[..]);
+                assert_eq!(data.len(), if compressed { 34 } else { 33 });
+            }
+        }
+
+        let mut data = vec![PUBKEY_ADDRESS_PREFIX_MAIN];
+        data.extend_from_slice(&inner[..]);
+        assert!(matches!(
+            PrivateKey::from_wif(&base58::encode_check(&data)),
+            Err(FromWifError::InvalidAddressVersion(_))
+        ));
+        data[0] = SECRET_KEY_PREFIX_MAIN;
+        data.extend_from_slice(&[1, 1]);
+        assert!(matches!(
+            PrivateKey::from_wif(&base58::encode_check(&data)),
+            Err(FromWifError::InvalidBase58PayloadLength(_))
+        ));
+    }
 
     #[test]
     fn test_key_derivation() {
@@ -1439,6 +1481,34 @@ struct Vector {
         }
     }
 
+    #[test]
+    fn tap_tweak() {
+        let secp = Secp256k1::new();
+
+        // The first script pubkey test vector from BIP-341, it has no script tree.
+        let internal_key = "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d"
+            .parse::<UntweakedPublicKey>()
+            .unwrap();
+        let (output_key, parity) = internal_key.tap_tweak(&secp, None);
+        assert_eq!(
+            output_key.to_inner(),
+            "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"
+                .parse::<XOnlyPublicKey>()
+                .unwrap()
+        );
+        let tweak = TapTweakHash::from_key_and_tweak(internal_key, None).to_scalar();
+        assert!(internal_key.tweak_add_check(&secp, &output_key.to_inner(), parity, tweak));
+
+        // Tweaking a key pair gives the same output key and parity as tweaking its public key.
+        let sk = secp256k1::SecretKey::from_slice(&[0x01; 32]).unwrap();
+        let keypair = Keypair::from_secret_key(&secp, &sk);
+        let (xonly, _parity) = XOnlyPublicKey::from_keypair(&keypair);
+        for merkle_root in [None, Some(TapNodeHash::assume_hidden([0x02; 32]))] {
+            let tweaked = keypair.tap_tweak(&secp, merkle_root);
+            assert_eq!(tweaked.public_parts(), xonly.tap_tweak(&secp, merkle_root));
+        }
+    }
+
     #[test]
     #[cfg(feature = "rand-std")]
     fn public_key_constructors() {
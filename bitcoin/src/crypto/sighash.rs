@@ -127,6 +127,12 @@ struct TaprootCache {
 
 /// Contains outputs of previous transactions. In the case [`TapSighashType`] variant is
 /// `SIGHASH_ANYONECANPAY`, [`Prevouts::One`] may be used.
+///
+/// The Taproot sighash functions check that the prevouts match the sighash type: without
+/// `SIGHASH_ANYONECANPAY` all of them are committed to, so passing [`Prevouts::One`] fails with
+/// [`PrevoutsKindError`] and passing [`Prevouts::All`] with a different number of outputs than
+/// there are inputs fails with [`PrevoutsSizeError`]. Segwit v0 sighashes only commit to the value
+/// of the output being spent, so those functions take an [`Amount`] instead.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum Prevouts<'u, T>
 where
@@ -872,6 +878,10 @@ pub fn p2wpkh_signature_hash(
     }
 
     /// Computes the BIP143 sighash to spend a p2wsh transaction for any flag type.
+    ///
+    /// The `witness_script` is committed to as the script code unchanged, so this computes the
+    /// segwit v0 sighash for any script code (see [`Self::segwit_v0_encode_signing_data_to`]).
+    #[doc(alias = "segwit_v0_signature_hash")]
     pub fn p2wsh_signature_hash(
         &mut self,
         input_index: usize,
@@ -1498,6 +1508,57 @@ fn sighash_single_bug() {
         assert_eq!(got, want)
     }
 
+    #[test]
+    fn legacy_sighash_types() {
+        use crate::constants::genesis_block;
+        use crate::Network;
+
+        // Spend the genesis output, its script pubkey is a P2PK.
+        let genesis = genesis_block(Network::Bitcoin);
+        let coinbase = &genesis.txdata[0];
+        let script_pubkey = &coinbase.output[0].script_pubkey;
+        let spend = |outpoint| TxIn { previous_output: outpoint, ..TxIn::default() };
+        let mut tx = Transaction {
+            version: transaction::Version::ONE,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![
+                spend(transaction::OutPoint { txid: coinbase.compute_txid(), vout: 0 }),
+                spend(transaction::OutPoint::NULL),
+            ],
+            output: vec![TxOut {
+                value: Amount::from_int_btc(49),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        };
+        let sighash = |tx: &Transaction, index, ty: EcdsaSighashType| {
+            SighashCache::new(tx).legacy_signature_hash(index, script_pubkey, ty.to_u32()).unwrap()
+        };
+
+        let types = [
+            EcdsaSighashType::All,
+            EcdsaSighashType::None,
+            EcdsaSighashType::Single,
+            EcdsaSighashType::AllPlusAnyoneCanPay,
+            EcdsaSighashType::NonePlusAnyoneCanPay,
+            EcdsaSighashType::SinglePlusAnyoneCanPay,
+        ];
+        let hashes = types.iter().map(|ty| sighash(&tx, 0, *ty)).collect::<Vec<_>>();
+        for (i, a) in hashes.iter().enumerate() {
+            assert!(hashes[i + 1..].iter().all(|b| a != b));
+        }
+
+        // The second input has no matching output.
+        let one = LegacySighash::from_byte_array(UINT256_ONE);
+        assert_eq!(sighash(&tx, 1, EcdsaSighashType::Single), one);
+        assert_eq!(sighash(&tx, 1, EcdsaSighashType::SinglePlusAnyoneCanPay), one);
+        assert_ne!(sighash(&tx, 1, EcdsaSighashType::All), one);
+
+        // ANYONECANPAY does not commit to the other inputs.
+        tx.input[1].previous_output.vout = 1;
+        assert_ne!(sighash(&tx, 0, EcdsaSighashType::All), hashes[0]);
+        assert_eq!(sighash(&tx, 0, EcdsaSighashType::AllPlusAnyoneCanPay), hashes[3]);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn legacy_sighash() {
@@ -1727,6 +1788,69 @@ fn test_sighash_errors() {
         );
     }
 
+    #[test]
+    fn prevouts_checks() {
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn::default(), TxIn::default()],
+            output: vec![],
+        };
+        let two = [TxOut::NULL, TxOut::NULL];
+
+        let all = Prevouts::All(&two);
+        assert_eq!(all.check_all(&tx), Ok(()));
+        assert_eq!(all.get_all().map(<[_]>::len), Ok(2));
+        assert_eq!(all.get(1), Ok(&TxOut::NULL));
+        assert_eq!(all.get(2), Err(PrevoutsIndexError::InvalidAllIndex));
+        assert_eq!(Prevouts::All(&two[..1]).check_all(&tx), Err(PrevoutsSizeError));
+
+        let one = Prevouts::One(1, TxOut::NULL);
+        assert_eq!(one.check_all(&tx), Ok(()));
+        assert_eq!(one.get_all().map(<[_]>::len), Err(PrevoutsKindError));
+        assert_eq!(one.get(1), Ok(&TxOut::NULL));
+        assert_eq!(one.get(0), Err(PrevoutsIndexError::InvalidOneIndex));
+    }
+
+    #[test]
+    fn taproot_prevouts_for_anyonecanpay() {
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn::default(), TxIn::default()],
+            output: vec![TxOut::NULL, TxOut::NULL],
+        };
+        let spent = |sat| TxOut { value: Amount::from_sat(sat), script_pubkey: ScriptBuf::new() };
+        let prevouts = [spent(1_000), spent(2_000)];
+        let all = Prevouts::All(&prevouts);
+        let one = Prevouts::One(1, prevouts[1].clone());
+        let leaf_hash = ScriptPath::with_defaults(Script::new()).leaf_hash();
+        let mut cache = SighashCache::new(&tx);
+
+        // Only the spent output of the signed input is committed to.
+        for ty in [TapSighashType::AllPlusAnyoneCanPay, TapSighashType::SinglePlusAnyoneCanPay] {
+            assert_eq!(
+                cache.taproot_key_spend_signature_hash(1, &all, ty).unwrap(),
+                cache.taproot_key_spend_signature_hash(1, &one, ty).unwrap(),
+            );
+            assert_eq!(
+                cache.taproot_script_spend_signature_hash(1, &all, leaf_hash, ty).unwrap(),
+                cache.taproot_script_spend_signature_hash(1, &one, leaf_hash, ty).unwrap(),
+            );
+        }
+
+        // Key path and script path spends commit to different data.
+        let key = cache.taproot_key_spend_signature_hash(1, &all, TapSighashType::All).unwrap();
+        let script = cache
+            .taproot_script_spend_signature_hash(1, &all, leaf_hash, TapSighashType::All)
+            .unwrap();
+        assert_ne!(key, script);
+        assert_eq!(
+            cache.taproot_signature_hash(1, &all, None, None, TapSighashType::All).unwrap(),
+            key
+        );
+    }
+
     #[test]
     fn test_annex_errors() {
         assert_eq!(Annex::new(&[]), Err(AnnexError::Empty));
@@ -2045,6 +2169,45 @@ fn bip143_p2wpkh() {
         );
     }
 
+    #[test]
+    fn bip143_cache_reuse() {
+        use crate::address::script_pubkey::ScriptExt as _;
+
+        // The transaction from the native P2WPKH example above.
+        let tx = deserialize::<Transaction>(
+            &hex!(
+                "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f000000\
+                0000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a01000000\
+                00ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093\
+                510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000"
+            ),
+        ).unwrap();
+        let spk = ScriptBuf::from_hex("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        let script_code = spk.p2wpkh_script_code().unwrap();
+        let value = Amount::from_sat(600_000_000);
+        let want = "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+            .parse::<SegwitV0Sighash>()
+            .unwrap();
+
+        // Passing the script code explicitly gives the same hash.
+        let mut cache = SighashCache::new(&tx);
+        assert_eq!(
+            cache.p2wsh_signature_hash(1, &script_code, value, EcdsaSighashType::All).unwrap(),
+            want
+        );
+
+        // Hashes computed from a populated cache match the ones from a fresh cache.
+        for ty in [EcdsaSighashType::All, EcdsaSighashType::None, EcdsaSighashType::Single] {
+            let fresh =
+                SighashCache::new(&tx).p2wsh_signature_hash(0, &script_code, value, ty).unwrap();
+            assert_eq!(cache.p2wsh_signature_hash(0, &script_code, value, ty).unwrap(), fresh);
+        }
+        assert_eq!(
+            cache.p2wpkh_signature_hash(1, &spk, value, EcdsaSighashType::All).unwrap(),
+            want
+        );
+    }
+
     #[test]
     fn bip143_p2wpkh_nested_in_p2sh() {
         let tx = deserialize::<Transaction>(
@@ -19,7 +19,7 @@
 use crate::consensus::encode::{self, Decodable, Encodable, MAX_VEC_SIZE};
 use crate::merkle_tree::{MerkleNode as _, TxMerkleNode};
 use crate::prelude::Vec;
-use crate::transaction::{Transaction, Txid};
+use crate::transaction::Txid;
 use crate::Weight;
 
 /// Data structure that represents a block header paired to a partial Merkle tree.
@@ -75,7 +75,7 @@ pub fn from_block_with_predicate<F>(block: &Block, match_txids: F) -> Self
     where
         F: Fn(&Txid) -> bool,
     {
-        let block_txids: Vec<_> = block.txdata.iter().map(Transaction::compute_txid).collect();
+        let block_txids: Vec<_> = block.txids().collect();
         Self::from_header_txids_with_predicate(&block.header, &block_txids, match_txids)
     }
 
@@ -716,6 +716,28 @@ fn merkleblock_construct_from_txids_not_found() {
         assert_eq!(index.len(), 0);
     }
 
+    #[test]
+    fn merkleblock_extract_matches_checks_header() {
+        let block = get_block_13b8a();
+        let wanted: Vec<Txid> = block.txids().step_by(3).collect();
+
+        let mut merkle_block =
+            MerkleBlock::from_block_with_predicate(&block, |t| wanted.contains(t));
+
+        let mut matches: Vec<Txid> = vec![];
+        let mut index: Vec<u32> = vec![];
+        merkle_block.extract_matches(&mut matches, &mut index).unwrap();
+        assert_eq!(matches, wanted);
+        assert_eq!(index, [0, 3, 6]);
+
+        // A proof is only valid against the header it was built for.
+        merkle_block.header.merkle_root = TxMerkleNode::from_byte_array([0; 32]);
+        assert_eq!(
+            merkle_block.extract_matches(&mut vec![], &mut vec![]),
+            Err(MerkleBlockError::MerkleRootMismatch)
+        );
+    }
+
     #[cfg(feature = "rand-std")]
     impl PartialMerkleTree {
         /// Flip one bit in one of the hashes - this should break the authentication
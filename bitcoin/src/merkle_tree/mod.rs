@@ -119,6 +119,16 @@ fn combine(&self, other: &Self) -> Self {
     }
 }
 
+impl From<Txid> for TxMerkleNode {
+    /// Converts a TXID into the leaf node it becomes in a block's transaction Merkle tree.
+    fn from(txid: Txid) -> Self { Self::from_leaf(txid) }
+}
+
+impl From<Wtxid> for WitnessMerkleNode {
+    /// Converts a WTXID into the leaf node it becomes in a block's witness Merkle tree.
+    fn from(wtxid: Wtxid) -> Self { Self::from_leaf(wtxid) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,4 +148,19 @@ fn static_vector() {
         let from_iter = TxMerkleNode::calculate_root(hashes_iter.clone());
         assert_eq!(from_iter, Some(block.header.merkle_root));
     }
+
+    #[test]
+    fn leaf_conversions() {
+        let block = crate::constants::genesis_block(crate::Network::Bitcoin);
+        let coinbase = &block.txdata[0];
+
+        let txid = coinbase.compute_txid();
+        assert_eq!(TxMerkleNode::from(txid), TxMerkleNode::from_leaf(txid));
+        assert_eq!(TxMerkleNode::from(txid).to_byte_array(), txid.to_byte_array());
+        // A single transaction is its own Merkle root.
+        assert_eq!(TxMerkleNode::from(txid), block.header.merkle_root);
+
+        let wtxid = coinbase.compute_wtxid();
+        assert_eq!(WitnessMerkleNode::from(wtxid), WitnessMerkleNode::from_leaf(wtxid));
+    }
 }
@@ -27,7 +27,10 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::constants::ChainHash;
+use crate::constants::{
+    ChainHash, PUBKEY_ADDRESS_PREFIX_MAIN, PUBKEY_ADDRESS_PREFIX_TEST, SCRIPT_ADDRESS_PREFIX_MAIN,
+    SCRIPT_ADDRESS_PREFIX_TEST,
+};
 use crate::p2p::Magic;
 use crate::prelude::{String, ToOwned};
 
@@ -57,7 +60,7 @@ fn from(n: Network) -> Self {
 
         match n {
             Bitcoin => NetworkKind::Main,
-            Testnet | Signet | Regtest => NetworkKind::Test,
+            Testnet | Testnet4 | Signet | Regtest => NetworkKind::Test,
         }
     }
 }
@@ -70,14 +73,23 @@ fn from(n: Network) -> Self {
 pub enum Network {
     /// Mainnet Bitcoin.
     Bitcoin,
-    /// Bitcoin's testnet network.
+    /// Bitcoin's testnet network (testnet3).
     Testnet,
+    /// Bitcoin's testnet4 network, see [BIP-94].
+    ///
+    /// [BIP-94]: <https://github.com/bitcoin/bips/blob/master/bip-0094.mediawiki>
+    Testnet4,
     /// Bitcoin's signet network.
     Signet,
     /// Bitcoin's regtest network.
     Regtest,
 }
 
+/// Every network, for tests that have to cover all of them.
+#[cfg(test)]
+pub(crate) const ALL_NETWORKS: [Network; 5] =
+    [Network::Bitcoin, Network::Testnet, Network::Testnet4, Network::Signet, Network::Regtest];
+
 impl Network {
     /// Creates a `Network` from the magic bytes.
     ///
@@ -113,12 +125,13 @@ pub fn magic(self) -> Magic { Magic::from(self) }
     /// Chain selection options:
     ///
     /// -chain=<chain>
-    /// Use the chain <chain> (default: main). Allowed values: main, test, signet, regtest
+    /// Use the chain <chain> (default: main). Allowed values: main, test, testnet4, signet, regtest
     /// ```
     pub fn to_core_arg(self) -> &'static str {
         match self {
             Network::Bitcoin => "main",
             Network::Testnet => "test",
+            Network::Testnet4 => "testnet4",
             Network::Signet => "signet",
             Network::Regtest => "regtest",
         }
@@ -131,7 +144,7 @@ pub fn to_core_arg(self) -> &'static str {
     /// Chain selection options:
     ///
     /// -chain=<chain>
-    /// Use the chain <chain> (default: main). Allowed values: main, test, signet, regtest
+    /// Use the chain <chain> (default: main). Allowed values: main, test, testnet4, signet, regtest
     /// ```
     pub fn from_core_arg(core_arg: &str) -> Result<Self, ParseNetworkError> {
         use Network::*;
@@ -139,6 +152,7 @@ pub fn from_core_arg(core_arg: &str) -> Result<Self, ParseNetworkError> {
         let network = match core_arg {
             "main" => Bitcoin,
             "test" => Testnet,
+            "testnet4" => Testnet4,
             "signet" => Signet,
             "regtest" => Regtest,
             _ => return Err(ParseNetworkError(core_arg.to_owned())),
@@ -173,16 +187,61 @@ pub fn from_chain_hash(chain_hash: ChainHash) -> Option<Network> {
         Network::try_from(chain_hash).ok()
     }
 
+    /// Returns the network whose base58 p2pkh addresses start with the version byte `prefix`.
+    ///
+    /// All test networks share the same prefix, for it this returns [`Network::Testnet`] but the
+    /// address may as well be for testnet4, signet or regtest. Use [`NetworkKind`] if only the
+    /// distinction between mainnet and the test networks matters.
+    pub fn from_pubkey_address_prefix(prefix: u8) -> Option<Network> {
+        match prefix {
+            PUBKEY_ADDRESS_PREFIX_MAIN => Some(Network::Bitcoin),
+            PUBKEY_ADDRESS_PREFIX_TEST => Some(Network::Testnet),
+            _ => None,
+        }
+    }
+
+    /// Returns the network whose base58 p2sh addresses start with the version byte `prefix`.
+    ///
+    /// As with [`Network::from_pubkey_address_prefix`] the test prefix returns
+    /// [`Network::Testnet`] for all test networks.
+    pub fn from_script_address_prefix(prefix: u8) -> Option<Network> {
+        match prefix {
+            SCRIPT_ADDRESS_PREFIX_MAIN => Some(Network::Bitcoin),
+            SCRIPT_ADDRESS_PREFIX_TEST => Some(Network::Testnet),
+            _ => None,
+        }
+    }
+
     /// Returns the associated network parameters.
     pub const fn params(self) -> &'static Params {
-        const PARAMS: [Params; 4] = [
+        const PARAMS: [Params; 5] = [
             Params::new(Network::Bitcoin),
             Params::new(Network::Testnet),
+            Params::new(Network::Testnet4),
             Params::new(Network::Signet),
             Params::new(Network::Regtest),
         ];
         &PARAMS[self as usize]
     }
+
+    /// Returns the `time` field of this network's genesis block header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bitcoin::Network;
+    ///
+    /// assert_eq!(Network::Bitcoin.genesis_block_time(), 1231006505);
+    /// ```
+    pub const fn genesis_block_time(self) -> u32 {
+        match self {
+            Network::Bitcoin => 1231006505,
+            Network::Testnet => 1296688602,
+            Network::Testnet4 => 1714777860,
+            Network::Signet => 1598918400,
+            Network::Regtest => 1296688602,
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -212,7 +271,7 @@ fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Self::Value, E> {
                 Network::from_core_arg(s).map_err(|_| {
                     E::invalid_value(
                         serde::de::Unexpected::Str(s),
-                        &"bitcoin network encoded as a string (either main, test, signet or regtest)",
+                        &"bitcoin network encoded as a string (either main, test, testnet4, signet or regtest)",
                     )
                 })
             }
@@ -255,6 +314,7 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
         let network = match s {
             "bitcoin" => Bitcoin,
             "testnet" => Testnet,
+            "testnet4" => Testnet4,
             "signet" => Signet,
             "regtest" => Regtest,
             _ => return Err(ParseNetworkError(s.to_owned())),
@@ -270,6 +330,7 @@ fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let s = match *self {
             Bitcoin => "bitcoin",
             Testnet => "testnet",
+            Testnet4 => "testnet4",
             Signet => "signet",
             Regtest => "regtest",
         };
@@ -301,6 +362,7 @@ fn try_from(chain_hash: ChainHash) -> Result<Self, Self::Error> {
             // Note: any new network entries must be matched against here.
             ChainHash::BITCOIN => Ok(Network::Bitcoin),
             ChainHash::TESTNET => Ok(Network::Testnet),
+            ChainHash::TESTNET4 => Ok(Network::Testnet4),
             ChainHash::SIGNET => Ok(Network::Signet),
             ChainHash::REGTEST => Ok(Network::Regtest),
             _ => Err(UnknownChainHashError(chain_hash)),
@@ -310,7 +372,7 @@ fn try_from(chain_hash: ChainHash) -> Result<Self, Self::Error> {
 
 #[cfg(test)]
 mod tests {
-    use super::Network;
+    use super::{Network, ALL_NETWORKS};
     use crate::consensus::encode::{deserialize, serialize};
     use crate::p2p::ServiceFlags;
 
@@ -416,6 +478,71 @@ fn from_to_core_arg() {
         }
     }
 
+    #[test]
+    fn testnet4_magic_and_names() {
+        let magic = Network::Testnet4.magic();
+        assert_eq!(serialize(&magic), &[0x1c, 0x16, 0x3f, 0x28]);
+        assert_eq!(deserialize(&[0x1c, 0x16, 0x3f, 0x28]).ok(), Some(magic));
+
+        assert_eq!(Network::Testnet4.to_string(), "testnet4");
+        assert_eq!("testnet4".parse::<Network>().unwrap(), Network::Testnet4);
+
+        assert_eq!(Network::Testnet4.to_core_arg(), "testnet4");
+        assert_eq!(Network::from_core_arg("testnet4"), Ok(Network::Testnet4));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip_testnet4() {
+        let got = serde_json::to_string(&Network::Testnet4).expect("failed to serialize network");
+        assert_eq!(got, "\"testnet4\"");
+
+        let back: Network = serde_json::from_str(&got).expect("failed to deserialize network");
+        assert_eq!(back, Network::Testnet4);
+    }
+
+    #[test]
+    fn all_networks_lists_every_variant() {
+        for (i, network) in ALL_NETWORKS.iter().enumerate() {
+            // Fails to compile when a network is added, add it to `ALL_NETWORKS` too.
+            let index = match network {
+                Network::Bitcoin => 0,
+                Network::Testnet => 1,
+                Network::Testnet4 => 2,
+                Network::Signet => 3,
+                Network::Regtest => 4,
+            };
+            assert_eq!(index, i);
+        }
+    }
+
+    #[test]
+    fn genesis_block_time() {
+        use crate::constants::genesis_block;
+
+        assert_eq!(Network::Bitcoin.genesis_block_time(), 1231006505);
+        assert_eq!(Network::Testnet.genesis_block_time(), 1296688602);
+        assert_eq!(Network::Signet.genesis_block_time(), 1598918400);
+        assert_eq!(Network::Regtest.genesis_block_time(), 1296688602);
+
+        for network in ALL_NETWORKS {
+            assert_eq!(genesis_block(network).header.time, network.genesis_block_time());
+        }
+    }
+
+    #[test]
+    fn from_address_prefix() {
+        assert_eq!(Network::from_pubkey_address_prefix(0), Some(Network::Bitcoin));
+        assert_eq!(Network::from_pubkey_address_prefix(111), Some(Network::Testnet));
+        assert_eq!(Network::from_script_address_prefix(5), Some(Network::Bitcoin));
+        assert_eq!(Network::from_script_address_prefix(196), Some(Network::Testnet));
+
+        // The prefixes are not interchangeable.
+        assert_eq!(Network::from_pubkey_address_prefix(5), None);
+        assert_eq!(Network::from_script_address_prefix(0), None);
+        assert_eq!(Network::from_pubkey_address_prefix(0xff), None);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn serde_as_core_arg() {
@@ -115,6 +115,13 @@ pub struct Params {
     pub allow_min_difficulty_blocks: bool,
     /// Determines whether retargeting is disabled for this network or not.
     pub no_pow_retargeting: bool,
+    /// Determines whether the [BIP-94] difficulty adjustment rules are enforced.
+    ///
+    /// When set, a retarget is based on the target of the first block of the period rather than
+    /// the (possibly minimum difficulty) last one.
+    ///
+    /// [BIP-94]: <https://github.com/bitcoin/bips/blob/master/bip-0094.mediawiki>
+    pub enforce_bip94: bool,
 }
 
 /// The mainnet parameters.
@@ -127,6 +134,8 @@ pub struct Params {
 pub static MAINNET: Params = Params::MAINNET;
 /// The testnet parameters.
 pub static TESTNET: Params = Params::TESTNET;
+/// The testnet4 parameters.
+pub static TESTNET4: Params = Params::TESTNET4;
 /// The signet parameters.
 pub static SIGNET: Params = Params::SIGNET;
 /// The regtest parameters.
@@ -152,6 +161,7 @@ impl Params {
         pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
         allow_min_difficulty_blocks: false,
         no_pow_retargeting: false,
+        enforce_bip94: false,
     };
 
     /// The testnet parameters.
@@ -169,6 +179,25 @@ impl Params {
         pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
         allow_min_difficulty_blocks: true,
         no_pow_retargeting: false,
+        enforce_bip94: false,
+    };
+
+    /// The testnet4 parameters.
+    pub const TESTNET4: Params = Params {
+        network: Network::Testnet4,
+        bip16_time: 1333238400, // Apr 1 2012
+        bip34_height: BlockHeight::from_u32(1),
+        bip65_height: BlockHeight::from_u32(1),
+        bip66_height: BlockHeight::from_u32(1),
+        rule_change_activation_threshold: BlockInterval::from_u32(1512), // 75%
+        miner_confirmation_window: BlockInterval::from_u32(2016),
+        pow_limit: Target::MAX_ATTAINABLE_TESTNET4,
+        max_attainable_target: Target::MAX_ATTAINABLE_TESTNET4,
+        pow_target_spacing: 10 * 60,            // 10 minutes.
+        pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
+        allow_min_difficulty_blocks: true,
+        no_pow_retargeting: false,
+        enforce_bip94: true,
     };
 
     /// The signet parameters.
@@ -186,6 +215,7 @@ impl Params {
         pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
         allow_min_difficulty_blocks: false,
         no_pow_retargeting: false,
+        enforce_bip94: false,
     };
 
     /// The regtest parameters.
@@ -203,6 +233,7 @@ impl Params {
         pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
         allow_min_difficulty_blocks: true,
         no_pow_retargeting: true,
+        enforce_bip94: false,
     };
 
     /// Creates parameters set for the given network.    /// Creates parameters set for the given network.
@@ -210,6 +241,7 @@ pub const fn new(network: Network) -> Self {
         match network {
             Network::Bitcoin => Params::MAINNET,
             Network::Testnet => Params::TESTNET,
+            Network::Testnet4 => Params::TESTNET4,
             Network::Signet => Params::SIGNET,
             Network::Regtest => Params::REGTEST,
         }
@@ -246,6 +278,7 @@ fn as_ref(&self) -> &Params {
         match *self {
             Network::Bitcoin => &MAINNET,
             Network::Testnet => &TESTNET,
+            Network::Testnet4 => &TESTNET4,
             Network::Signet => &SIGNET,
             Network::Regtest => &REGTEST,
         }
@@ -218,6 +218,8 @@ impl Magic {
     pub const BITCOIN: Self = Self([0xF9, 0xBE, 0xB4, 0xD9]);
     /// Bitcoin testnet network magic bytes.
     pub const TESTNET: Self = Self([0x0B, 0x11, 0x09, 0x07]);
+    /// Bitcoin testnet4 network magic bytes.
+    pub const TESTNET4: Self = Self([0x1C, 0x16, 0x3F, 0x28]);
     /// Bitcoin signet network magic bytes.
     pub const SIGNET: Self = Self([0x0A, 0x03, 0xCF, 0x40]);
     /// Bitcoin regtest network magic bytes.
@@ -227,7 +229,7 @@ impl Magic {
     pub const fn from_bytes(bytes: [u8; 4]) -> Magic { Magic(bytes) }
 
     /// Get network magic bytes.
-    pub fn to_bytes(self) -> [u8; 4] { self.0 }
+    pub const fn to_bytes(self) -> [u8; 4] { self.0 }
 
     /// Returns the magic bytes for the network defined by `params`.
     pub fn from_params(params: impl AsRef<Params>) -> Self { params.as_ref().network.into() }
@@ -274,6 +276,7 @@ fn try_from(magic: Magic) -> Result<Self, Self::Error> {
 generate_network_magic_conversion! {
     Network::Bitcoin => Magic::BITCOIN,
     Network::Testnet => Magic::TESTNET,
+    Network::Testnet4 => Magic::TESTNET4,
     Network::Signet => Magic::SIGNET,
     Network::Regtest => Magic::REGTEST,
 }
@@ -441,4 +444,30 @@ fn magic_from_str() {
             assert_eq!(&magic.to_string(), magic_str);
         }
     }
+
+    #[test]
+    fn magic_from_str_testnet4() {
+        let magic: Magic = "1c163f28".parse().unwrap();
+        assert_eq!(magic, Magic::TESTNET4);
+        assert_eq!(Network::try_from(magic).unwrap(), Network::Testnet4);
+        assert_eq!(magic.to_string(), "1c163f28");
+    }
+
+    #[test]
+    fn magic_bytes() {
+        use crate::consensus::encode::serialize;
+        use crate::network::ALL_NETWORKS;
+        use crate::prelude::DisplayHex;
+
+        for network in ALL_NETWORKS {
+            let magic = network.magic();
+            assert_eq!(Magic::from_bytes(magic.to_bytes()), magic);
+            assert_eq!(magic.to_string(), magic.to_bytes().to_lower_hex_string());
+            // The magic is sent as is, not as a little-endian integer.
+            assert_eq!(serialize(&magic), magic.to_bytes());
+        }
+        assert_eq!(Network::Bitcoin.magic().to_bytes(), [0xF9, 0xBE, 0xB4, 0xD9]);
+        assert_eq!(Network::Bitcoin.magic().to_string(), "f9beb4d9");
+        assert_eq!(format!("{:X}", Network::Bitcoin.magic()), "F9BEB4D9");
+    }
 }
@@ -5,7 +5,8 @@
 //! Provides the [`Work`] and [`Target`] types that are used in proof-of-work calculations. The
 //! functions here are designed to be fast, by that we mean it is safe to use them to check headers.
 
-use core::ops::{Add, Div, Mul, Not, Rem, Shl, Shr, Sub};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, Mul, Not, Rem, Shl, Shr, Sub};
 use core::{cmp, fmt};
 
 use internals::impl_to_hex_from_lower_hex;
@@ -97,6 +98,9 @@ fn fmt(&self, f: &mut fmt::Formatter) -> core::fmt::Result {
 pub struct Work(U256);
 
 impl Work {
+    /// No work.
+    pub const ZERO: Self = Work(U256::ZERO);
+
     /// Converts this [`Work`] to [`Target`].
     pub fn to_target(self) -> Target { Target(self.0.inverse()) }
 
@@ -116,11 +120,24 @@ impl Add for Work {
     fn add(self, rhs: Self) -> Self { Work(self.0 + rhs.0) }
 }
 
+impl AddAssign for Work {
+    fn add_assign(&mut self, rhs: Self) { *self = *self + rhs }
+}
+
 impl Sub for Work {
     type Output = Work;
     fn sub(self, rhs: Self) -> Self { Work(self.0 - rhs.0) }
 }
 
+impl Sum for Work {
+    /// Sums the work of a chain of headers, e.g. `headers.iter().map(Header::work).sum()`.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Work::ZERO, Add::add) }
+}
+
+impl<'a> Sum<&'a Work> for Work {
+    fn sum<I: Iterator<Item = &'a Work>>(iter: I) -> Self { iter.copied().sum() }
+}
+
 /// A 256 bit integer representing target.
 ///
 /// The SHA-256 hash of a block's header must be lower than or equal to the current target for the
@@ -157,6 +174,10 @@ impl Target {
     // https://github.com/bitcoin/bitcoin/blob/8105bce5b384c72cf08b25b7c5343622754e7337/src/kernel/chainparams.cpp#L208
     pub const MAX_ATTAINABLE_TESTNET: Self = Target(U256(0xFFFF_u128 << (208 - 128), 0));
 
+    /// The proof of work limit on testnet4.
+    // Same as testnet3, see `CTestNet4Params` in Bitcoin Core's `src/kernel/chainparams.cpp`.
+    pub const MAX_ATTAINABLE_TESTNET4: Self = Target(U256(0xFFFF_u128 << (208 - 128), 0));
+
     /// The proof of work limit on regtest.
     // Taken from Bitcoin Core but had lossy conversion to/from compact form.
     // https://github.com/bitcoin/bitcoin/blob/8105bce5b384c72cf08b25b7c5343622754e7337/src/kernel/chainparams.cpp#L411
@@ -167,6 +188,12 @@ impl Target {
     // https://github.com/bitcoin/bitcoin/blob/8105bce5b384c72cf08b25b7c5343622754e7337/src/kernel/chainparams.cpp#L348
     pub const MAX_ATTAINABLE_SIGNET: Self = Target(U256(0x0377_ae00 << 80, 0));
 
+    /// Returns the proof of work limit (maximum attainable target) for the given network.
+    ///
+    /// This is the same as [`Params::max_attainable_target`]. Note that it differs from
+    /// [`Target::MAX`], which is the difficulty 1 target.
+    pub fn max_value(params: impl AsRef<Params>) -> Target { params.as_ref().max_attainable_target }
+
     /// Computes the [`Target`] value from a compact representation.
     ///
     /// ref: <https://developer.bitcoin.org/reference/block_chain.html#target-nbits>
@@ -220,8 +247,7 @@ pub fn to_compact_lossy(self) -> CompactTarget {
     /// to the target.
     #[cfg_attr(all(test, mutate), mutate)]
     pub fn is_met_by(&self, hash: BlockHash) -> bool {
-        let hash = U256::from_le_bytes(hash.to_byte_array());
-        hash <= self.0
+        Target::from_le_bytes(hash.to_byte_array()) <= *self
     }
 
     /// Converts this [`Target`] to [`Work`].
@@ -352,6 +378,32 @@ fn from_unprefixed_hex(s: &str) -> Result<CompactTarget, UnprefixedHexError> {
             Ok(Self::from_consensus(target))
         }
 
+        /// Creates a `CompactTarget` from a consensus encoded `u32`, rejecting invalid encodings.
+        ///
+        /// ref: <https://github.com/bitcoin/bitcoin/blob/0503cbea9aab47ec0a87d34611e5453158727169/src/arith_uint256.cpp>
+        ///
+        /// Unlike [`CompactTarget::from_consensus`] this rejects the encodings that Bitcoin Core's
+        /// `SetCompact` flags as negative (the sign bit of a non-zero mantissa is set) or as
+        /// overflowing 256 bits, a header with such `bits` is never valid. This does not check the
+        /// target against a network's proof-of-work limit.
+        fn try_from_consensus(bits: u32) -> Result<CompactTarget, CompactTargetError> {
+            let size = bits >> 24;
+            let mut word = bits & 0x007F_FFFF;
+            if size <= 3 {
+                word >>= 8 * (3 - size);
+            }
+
+            if word != 0 && bits & 0x0080_0000 != 0 {
+                return Err(CompactTargetError::Negative(bits));
+            }
+            if word != 0
+                && (size > 34 || (word > 0xFF && size > 33) || (word > 0xFFFF && size > 32))
+            {
+                return Err(CompactTargetError::Overflow(bits));
+            }
+            Ok(CompactTarget::from_consensus(bits))
+        }
+
         /// Computes the [`CompactTarget`] from a difficulty adjustment.
         ///
         /// ref: <https://github.com/bitcoin/bitcoin/blob/0503cbea9aab47ec0a87d34611e5453158727169/src/pow.cpp>
@@ -427,6 +479,108 @@ fn from_header_difficulty_adjustment(
             let bits = current.bits;
             CompactTarget::from_next_work_required(bits, timespan.into(), params)
         }
+
+        /// Computes the [`CompactTarget`] required for the block following `last`.
+        ///
+        /// ref: <https://github.com/bitcoin/bitcoin/blob/0503cbea9aab47ec0a87d34611e5453158727169/src/pow.cpp>
+        ///
+        /// `last` is the header at height `last_height` and `new_block_time` is the timestamp of the
+        /// block being validated or mined. `ancestor` must return the header at the given height of
+        /// the chain ending at `last`, it is only called with heights below `last_height`.
+        ///
+        /// If the new block is at a difficulty adjustment height the target is recalculated as in
+        /// [`CompactTarget::from_next_work_required`], otherwise the target of `last` is kept.
+        ///
+        /// On networks with [`Params::allow_min_difficulty_blocks`] set (e.g. testnet) a block more
+        /// than twice the target spacing after its predecessor may be mined at the minimum
+        /// difficulty. Subsequent blocks return to the target of the last block that was not mined
+        /// under this rule, found by walking back through `ancestor` until the start of the
+        /// difficulty adjustment period.
+        ///
+        /// # Returns
+        ///
+        /// The expected [`CompactTarget`] of the block at height `last_height + 1`.
+        fn next_work_required<F: FnMut(u32) -> Header>(
+            last: Header,
+            last_height: u32,
+            new_block_time: u32,
+            mut ancestor: F,
+            params: impl AsRef<Params>,
+        ) -> CompactTarget {
+            let params = params.as_ref();
+            let interval = params.difficulty_adjustment_interval();
+            let pow_limit = params.pow_limit.to_compact_lossy();
+
+            if (u64::from(last_height) + 1) % interval != 0 {
+                if !params.allow_min_difficulty_blocks {
+                    return last.bits;
+                }
+                // Special difficulty rule for testnet, if the new block is more than twice the
+                // target spacing after the last block then allow mining a min-difficulty block.
+                if u64::from(new_block_time) > u64::from(last.time) + params.pow_target_spacing * 2 {
+                    return pow_limit;
+                }
+                // Otherwise return the target of the last non-special-min-difficulty block.
+                let (mut header, mut height) = (last, last_height);
+                while height > 0 && u64::from(height) % interval != 0 && header.bits == pow_limit {
+                    height -= 1;
+                    header = ancestor(height);
+                }
+                return header.bits;
+            }
+
+            // The off-by-one is inherited from Core, see `from_next_work_required`.
+            let first = ancestor(last_height + 1 - interval as u32);
+            // Timestamps are not monotonic, a negative timespan is clamped like any short one.
+            let timespan = u64::from(last.time.saturating_sub(first.time));
+            // BIP-94: the last block may be a min-difficulty one, so retarget from the first.
+            let bits = if params.enforce_bip94 { first.bits } else { last.bits };
+            CompactTarget::from_next_work_required(bits, timespan, params)
+        }
+
+        /// Returns true if `self` is a permitted target for the block at `height` whose
+        /// predecessor had target `prev`.
+        ///
+        /// ref: <https://github.com/bitcoin/bitcoin/blob/0503cbea9aab47ec0a87d34611e5453158727169/src/pow.cpp>
+        ///
+        /// This only looks at the two targets and so, unlike [`CompactTarget::next_work_required`],
+        /// does not need the timestamps of the chain. Outside a difficulty adjustment height the
+        /// target must stay the same. At an adjustment height it may change by at most the factor
+        /// of 4 allowed by the retarget clamp (and may not exceed the network's maximum target).
+        ///
+        /// Networks with [`Params::allow_min_difficulty_blocks`] set permit any transition since a
+        /// block may fall back to the minimum difficulty at any time.
+        fn is_valid_transition(
+            &self,
+            prev: CompactTarget,
+            height: u32,
+            params: impl AsRef<Params>,
+        ) -> bool {
+            let params = params.as_ref();
+            if params.allow_min_difficulty_blocks {
+                return true;
+            }
+
+            if u64::from(height) % params.difficulty_adjustment_interval() != 0 {
+                return *self == prev;
+            }
+
+            let min_timespan = params.pow_target_timespan >> 2;
+            let max_timespan = params.pow_target_timespan << 2;
+            // Compare against the compact forms so that the rounding matches the retarget itself.
+            let largest = Target::from_compact(CompactTarget::from_next_work_required(
+                prev,
+                max_timespan,
+                params,
+            ));
+            let smallest = Target::from_compact(CompactTarget::from_next_work_required(
+                prev,
+                min_timespan,
+                params,
+            ));
+            let new = Target::from_compact(*self);
+            smallest <= new && new <= largest
+        }
     }
 }
 
@@ -448,6 +602,40 @@ fn consensus_decode<R: BufRead + ?Sized>(r: &mut R) -> Result<Self, encode::Erro
     }
 }
 
+/// An invalid compact target encoding, see [`CompactTarget::try_from_consensus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompactTargetError {
+    /// The mantissa is non-zero and has its sign bit set.
+    Negative(u32),
+    /// The encoded target does not fit in 256 bits.
+    Overflow(u32),
+}
+
+internals::impl_from_infallible!(CompactTargetError);
+
+impl fmt::Display for CompactTargetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use CompactTargetError::*;
+
+        match *self {
+            Negative(bits) => write!(f, "compact target {:#010x} is negative", bits),
+            Overflow(bits) => write!(f, "compact target {:#010x} overflows 256 bits", bits),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompactTargetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use CompactTargetError::*;
+
+        match *self {
+            Negative(_) | Overflow(_) => None,
+        }
+    }
+}
+
 /// Big-endian 256 bit integer type.
 // (high, low): u.0 contains the high bits, u.1 contains the low bits.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -1730,6 +1918,23 @@ fn compact_target_from_hex_invalid_hex_should_err() {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn target_and_work_hex() {
+        let target = Target::MAX_ATTAINABLE_MAINNET;
+        let hex = "00000000ffff0000000000000000000000000000000000000000000000000000";
+        assert_eq!(format!("{:x}", target), hex);
+        assert_eq!(format!("{:X}", target), hex.to_uppercase());
+        assert_eq!(format!("{:?}", target), format!("Target(0x{})", hex));
+        assert_eq!(target.to_hex(), hex);
+
+        let work = target.to_work();
+        let hex = "0000000000000000000000000000000000000000000000000000000100010001";
+        assert_eq!(format!("{:x}", work), hex);
+        assert_eq!(format!("{:#x}", work), format!("0x{}", hex));
+        assert_eq!(format!("{:?}", work), format!("Work(0x{})", hex));
+        assert_eq!(Work::from_hex(&format!("0x{:x}", work)).unwrap(), work);
+    }
+
     #[test]
     fn compact_target_lower_hex_and_upper_hex() {
         assert_eq!(format!("{:08x}", CompactTarget::from_consensus(0x01D0F456)), "01d0f456");
@@ -1815,6 +2020,177 @@ fn compact_target_from_downwards_difficulty_adjustment_using_headers() {
         assert_eq!(adjustment, adjustment_bits);
     }
 
+    fn next_work_header(time: u32, bits: CompactTarget) -> Header {
+        use crate::block::Version;
+        use crate::TxMerkleNode;
+
+        Header {
+            version: Version::ONE,
+            prev_blockhash: BlockHash::all_zeros(),
+            merkle_root: TxMerkleNode::from_byte_array([0; 32]),
+            time,
+            bits,
+            nonce: 0,
+        }
+    }
+
+    #[test]
+    fn next_work_required_retarget() {
+        use crate::constants::genesis_block;
+
+        let params = Params::new(crate::Network::Signet);
+        let genesis = genesis_block(&params).header;
+        // Block 2015, the last block of the first difficulty adjustment period.
+        let last = next_work_header(1599332177, genesis.bits);
+        let ancestor = |height: u32| {
+            assert_eq!(height, 0);
+            genesis
+        };
+        let got = CompactTarget::next_work_required(last, 2015, last.time + 600, ancestor, &params);
+        assert_eq!(got, CompactTarget::from_consensus(503394215)); // Block 2016 compact target
+
+        // No adjustment within the period.
+        let got = CompactTarget::next_work_required(
+            last,
+            2014,
+            last.time + 600,
+            |_| unreachable!(),
+            &params,
+        );
+        assert_eq!(got, last.bits);
+    }
+
+    #[test]
+    fn next_work_required_retarget_bip94() {
+        let pow_limit = CompactTarget::from_consensus(0x1d00ffff);
+        let bits = CompactTarget::from_consensus(0x1a01aa3d);
+
+        // Block 6047 is the last one of its period and was mined at minimum difficulty.
+        let first = next_work_header(1_700_000_000, bits);
+        let last = next_work_header(first.time + 14 * 24 * 60 * 60, pow_limit);
+        let ancestor = |height: u32| {
+            assert_eq!(height, 4032);
+            first
+        };
+
+        // Testnet4 retargets from the first block of the period.
+        let params = Params::new(crate::Network::Testnet4);
+        assert!(params.enforce_bip94);
+        let got = CompactTarget::next_work_required(last, 6047, last.time + 600, ancestor, &params);
+        assert_eq!(got, bits);
+
+        // Testnet3 retargets from the min-difficulty last block.
+        let params = Params::new(crate::Network::Testnet);
+        assert!(!params.enforce_bip94);
+        let got = CompactTarget::next_work_required(last, 6047, last.time + 600, ancestor, &params);
+        assert_eq!(got, pow_limit);
+    }
+
+    #[test]
+    fn next_work_required_testnet_min_difficulty() {
+        let params = Params::new(crate::Network::Testnet);
+        let pow_limit = CompactTarget::from_consensus(0x1d00ffff);
+        let bits = CompactTarget::from_consensus(0x1a01aa3d);
+
+        let last = next_work_header(1_700_000_000, bits);
+        let twenty_minutes = 2 * params.pow_target_spacing as u32;
+        let walk = |_: u32| -> Header { unreachable!() };
+
+        // Exactly twenty minutes later is not enough to allow a min-difficulty block.
+        let got =
+            CompactTarget::next_work_required(last, 100, last.time + twenty_minutes, walk, &params);
+        assert_eq!(got, bits);
+        let got = CompactTarget::next_work_required(
+            last,
+            100,
+            last.time + twenty_minutes + 1,
+            walk,
+            &params,
+        );
+        assert_eq!(got, pow_limit);
+
+        // After min-difficulty blocks the difficulty returns to the pre-drop value.
+        let chain = [
+            next_work_header(1_700_000_000, bits),
+            next_work_header(1_700_001_201, pow_limit),
+            next_work_header(1_700_002_402, pow_limit),
+        ];
+        let got = CompactTarget::next_work_required(
+            chain[2],
+            102,
+            chain[2].time + 600,
+            |height| chain[height as usize - 100],
+            &params,
+        );
+        assert_eq!(got, bits);
+
+        // The walk back stops at the start of the difficulty adjustment period.
+        let chain = [
+            next_work_header(1_700_000_000, pow_limit),
+            next_work_header(1_700_001_201, pow_limit),
+        ];
+        let got = CompactTarget::next_work_required(
+            chain[1],
+            2017,
+            chain[1].time + 600,
+            |height| chain[height as usize - 2016],
+            &params,
+        );
+        assert_eq!(got, pow_limit);
+    }
+
+    #[test]
+    fn compact_target_try_from_consensus() {
+        use CompactTargetError::*;
+
+        for bits in [0x1d00_ffff, 0x207f_ffff, 0x2012_3456, 0x2200_00ff] {
+            assert_eq!(
+                CompactTarget::try_from_consensus(bits),
+                Ok(CompactTarget::from_consensus(bits))
+            );
+        }
+        // The sign and overflow checks only apply to a non-zero mantissa.
+        assert!(CompactTarget::try_from_consensus(0x0092_3456).is_ok());
+        assert!(CompactTarget::try_from_consensus(0x0480_0000).is_ok());
+
+        assert_eq!(CompactTarget::try_from_consensus(0x01fe_dcba), Err(Negative(0x01fe_dcba)));
+        assert_eq!(CompactTarget::try_from_consensus(0x0492_3456), Err(Negative(0x0492_3456)));
+        assert_eq!(CompactTarget::try_from_consensus(0x2112_3456), Err(Overflow(0x2112_3456)));
+        assert_eq!(CompactTarget::try_from_consensus(0x2300_00ff), Err(Overflow(0x2300_00ff)));
+        assert_eq!(CompactTarget::try_from_consensus(0xff12_3456), Err(Overflow(0xff12_3456)));
+    }
+
+    #[test]
+    fn compact_target_is_valid_transition() {
+        let params = Params::new(crate::Network::Bitcoin);
+        let genesis_bits = CompactTarget::from_consensus(0x1d00ffff);
+
+        // Block 32256, the first mainnet retarget to increase difficulty.
+        let bits_32256 = CompactTarget::from_consensus(0x1d00d86a);
+        assert!(bits_32256.is_valid_transition(genesis_bits, 32256, &params));
+        // The same change is not allowed in the middle of a period.
+        assert!(!bits_32256.is_valid_transition(genesis_bits, 32257, &params));
+        assert!(genesis_bits.is_valid_transition(genesis_bits, 32257, &params));
+        // Difficulty can not drop below the minimum.
+        let easier = CompactTarget::from_consensus(0x1d01ffff);
+        assert!(!easier.is_valid_transition(genesis_bits, 32256, &params));
+
+        // At most a factor of 4 in either direction.
+        let prev = CompactTarget::from_consensus(0x1b0404cb);
+        let up = CompactTarget::from_next_work_required(prev, 0, &params);
+        let down = CompactTarget::from_next_work_required(prev, u64::MAX, &params);
+        assert!(up.is_valid_transition(prev, 2016, &params));
+        assert!(down.is_valid_transition(prev, 2016, &params));
+        let too_hard = CompactTarget::from_consensus(up.to_consensus() - 1);
+        let too_easy = CompactTarget::from_consensus(down.to_consensus() + 1);
+        assert!(!too_hard.is_valid_transition(prev, 2016, &params));
+        assert!(!too_easy.is_valid_transition(prev, 2016, &params));
+
+        // Testnet allows min-difficulty blocks at any height.
+        let testnet = Params::new(crate::Network::Testnet);
+        assert!(genesis_bits.is_valid_transition(prev, 2017, &testnet));
+    }
+
     #[test]
     fn compact_target_from_maximum_upward_difficulty_adjustment() {
         let params = Params::new(crate::Network::Signet);
@@ -1847,6 +2223,28 @@ fn compact_target_from_adjustment_is_max_target() {
         assert_eq!(got, want);
     }
 
+    #[test]
+    fn genesis_bits_within_pow_limit() {
+        use crate::constants::genesis_block;
+        use crate::network::{Network, ALL_NETWORKS};
+
+        for network in ALL_NETWORKS {
+            let genesis = Target::from_compact(genesis_block(network).header.bits);
+            let limit = Target::max_value(network);
+            // Every genesis block is mined at its network's minimum difficulty.
+            assert_eq!(genesis, limit);
+        }
+
+        // Regtest is the easiest network and sits exactly at its limit.
+        assert_eq!(Target::max_value(Network::Regtest), Target::MAX_ATTAINABLE_REGTEST);
+        assert_eq!(
+            Target::from_compact(CompactTarget::from_consensus(0x207fffff)),
+            Target::MAX_ATTAINABLE_REGTEST
+        );
+        assert!(Target::max_value(Network::Regtest) > Target::MAX);
+        assert_eq!(Target::max_value(Network::Bitcoin), Target::MAX);
+    }
+
     #[test]
     fn target_from_compact() {
         // (nBits, target)
@@ -1875,6 +2273,70 @@ fn target_is_met_by_for_target_equals_hash() {
         assert!(target.is_met_by(hash));
     }
 
+    #[test]
+    fn target_le_bytes_round_trip() {
+        let mut bytes = [0_u8; 32];
+        bytes[0] = 0xef;
+        bytes[31] = 0x01;
+        let target = Target::from_le_bytes(bytes);
+        assert_eq!(target.to_le_bytes(), bytes);
+        assert_eq!(target, Target::from_be_bytes(target.to_be_bytes()));
+    }
+
+    #[test]
+    fn target_ordering_agrees_with_is_met_by() {
+        let hash = "ef537f25c895bfa782526529a9b63d97aa631564d5d789c2b765448c8635fb6c"
+            .parse::<BlockHash>()
+            .expect("failed to parse block hash");
+        let hash_as_target = Target::from_le_bytes(hash.to_byte_array());
+
+        let above = Target(hash_as_target.0 + U256::ONE);
+        let below = Target(hash_as_target.0 - U256::ONE);
+
+        assert!(below < hash_as_target && hash_as_target < above);
+        assert!(above.is_met_by(hash));
+        assert!(!below.is_met_by(hash));
+    }
+
+    #[test]
+    fn compact_target_ordering() {
+        let lower = CompactTarget::from_consensus(0x1c00_ffff);
+        let higher = CompactTarget::from_consensus(0x1d00_ffff);
+        assert!(lower < higher);
+        assert!(Target::from_compact(lower) < Target::from_compact(higher));
+    }
+
+    #[test]
+    fn compact_target_consensus_encoding() {
+        use crate::consensus::encode::{deserialize, serialize};
+        use crate::constants::genesis_block;
+        use crate::network::ALL_NETWORKS;
+
+        let bits = CompactTarget::from_consensus(0x1d00ffff);
+        assert_eq!(serialize(&bits), [0xff, 0xff, 0x00, 0x1d]);
+        assert_eq!(deserialize::<CompactTarget>(&[0xff, 0xff, 0x00, 0x1d]).unwrap(), bits);
+        assert!(deserialize::<CompactTarget>(&[0xff, 0xff, 0x00]).is_err());
+
+        // The bits are the 4 bytes following version, previous block hash, merkle root and time.
+        for network in ALL_NETWORKS {
+            let header = genesis_block(network).header;
+            let encoded = serialize(&header);
+            assert_eq!(encoded[72..76], serialize(&header.bits)[..]);
+            assert_eq!(deserialize::<CompactTarget>(&encoded[72..76]).unwrap(), header.bits);
+        }
+        // Signet's genesis bits as serialized in its block 0.
+        assert_eq!(serialize(&CompactTarget::from_consensus(0x1e0377ae)), [0xae, 0x77, 0x03, 0x1e]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn compact_target_serde() {
+        let bits = CompactTarget::from_consensus(0x1d00ffff);
+        let json = serde_json::to_string(&bits).unwrap();
+        assert_eq!(json, "486604799");
+        assert_eq!(serde_json::from_str::<CompactTarget>(&json).unwrap(), bits);
+    }
+
     #[test]
     fn max_target_from_compact() {
         // The highest possible target is defined as 0x1d00ffff
@@ -1927,6 +2389,32 @@ fn roundtrip_target_work() {
         assert_eq!(back, target)
     }
 
+    #[test]
+    fn chain_work_sum() {
+        use crate::constants::genesis_block;
+        use crate::Network;
+
+        // Work of a block at the mainnet minimum difficulty, 2^256 / (target + 1).
+        let min_work = Work(U256::from(0x1_0001_0001_u64));
+        let genesis = genesis_block(Network::Bitcoin).header;
+        assert_eq!(genesis.work(), min_work);
+
+        let headers = [genesis; 3];
+        let total: Work = headers.iter().map(Header::work).sum();
+        assert_eq!(total, Work(U256::from(3 * 0x1_0001_0001_u64)));
+
+        let works = [min_work, min_work];
+        assert_eq!(works.iter().sum::<Work>(), min_work + min_work);
+        assert_eq!(core::iter::empty::<Work>().sum::<Work>(), Work::ZERO);
+
+        let mut acc = Work::ZERO;
+        acc += min_work;
+        acc += min_work;
+        assert_eq!(acc, min_work + min_work);
+        // More work on a chain is what decides the best chain.
+        assert!(acc > min_work);
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn work_log2() {
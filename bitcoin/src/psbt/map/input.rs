@@ -394,6 +394,8 @@ pub fn combine(&mut self, other: Self) {
             self.non_witness_utxo = None; // Clear out any non-witness UTXO when we set a witness one
         }
 
+        combine!(sighash_type, self, other);
+
         self.partial_sigs.extend(other.partial_sigs);
         self.bip32_derivation.extend(other.bip32_derivation);
         self.ripemd160_preimages.extend(other.ripemd160_preimages);
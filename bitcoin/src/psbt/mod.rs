@@ -20,14 +20,16 @@
 use internals::write_err;
 use secp256k1::{Keypair, Message, Secp256k1, Signing, Verification};
 
+use crate::address::script_pubkey::{BuilderExt as _, ScriptBufExt as _, ScriptExt as _};
 use crate::bip32::{self, KeySource, Xpriv, Xpub};
 use crate::crypto::key::{PrivateKey, PublicKey};
 use crate::crypto::{ecdsa, taproot};
 use crate::key::{TapTweak, XOnlyPublicKey};
 use crate::prelude::{btree_map, BTreeMap, BTreeSet, Borrow, Box, Vec};
-use crate::script::ScriptExt as _;
+use crate::script::{Builder, PushBytes, Script, ScriptBuf, ScriptExt as _};
 use crate::sighash::{self, EcdsaSighashType, Prevouts, SighashCache};
 use crate::transaction::{self, Transaction, TxOut};
+use crate::witness::Witness;
 use crate::{Amount, FeeRate, TapLeafHash, TapSighashType};
 
 #[rustfmt::skip]                // Keep public re-exports separate.
@@ -489,6 +491,174 @@ fn bip32_sign_schnorr<C, K, T>(
         Ok(used)
     }
 
+    /// Finalizes the inputs of this PSBT that have enough partial signatures.
+    ///
+    /// Supports inputs spending P2PKH, P2WPKH and P2WSH outputs, the segwit ones also when nested
+    /// in P2SH. The witness script of a P2WSH input must be a bare `OP_CHECKMULTISIG` script. Only
+    /// partial signatures that are valid for the sighash of the input are used.
+    ///
+    /// For each finalized input the `final_script_sig` and `final_script_witness` are set and, as
+    /// required by BIP 174, all other fields except the UTXOs, proprietary and unknown fields are
+    /// cleared. Inputs that are already finalized are left untouched. Once all inputs are
+    /// finalized the signed transaction can be retrieved with [`Psbt::extract_tx`].
+    ///
+    /// # Errors
+    ///
+    /// A map of input index -> error for the inputs that could not be finalized, the other inputs
+    /// are finalized regardless.
+    pub fn finalize<C: Verification>(&mut self, secp: &Secp256k1<C>) -> Result<(), FinalizeErrors> {
+        let tx = self.unsigned_tx.clone(); // clone because we need to mutably borrow when finalizing.
+        let mut cache = SighashCache::new(&tx);
+
+        let mut errors = BTreeMap::new();
+
+        for i in 0..self.inputs.len() {
+            let input = &self.inputs[i];
+            if input.final_script_sig.is_some() || input.final_script_witness.is_some() {
+                continue;
+            }
+            match self.finalize_input(i, &mut cache, secp) {
+                Ok(input) => self.inputs[i] = input,
+                Err(e) => {
+                    errors.insert(i, e);
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns this PSBT's input at `input_index` in finalized form, see [`Psbt::finalize`].
+    fn finalize_input<C, T>(
+        &self,
+        input_index: usize,
+        cache: &mut SighashCache<T>,
+        secp: &Secp256k1<C>,
+    ) -> Result<Input, FinalizeError>
+    where
+        C: Verification,
+        T: Borrow<Transaction>,
+    {
+        use OutputType::*;
+
+        let output_type = self.output_type(input_index)?;
+        let spk = &self.spend_utxo(input_index)?.script_pubkey;
+        if matches!(output_type, Sh | Tr) || (output_type == Bare && !spk.is_p2pkh()) {
+            return Err(FinalizeError::UnsupportedScript);
+        }
+
+        let (msg, hash_ty) = self.sighash_ecdsa(input_index, cache)?;
+        let input = &self.inputs[input_index]; // Index checked in call to `sighash_ecdsa`.
+
+        // Check the scripts we were given are the ones committed to by the spent output.
+        if matches!(output_type, ShWpkh | ShWsh) {
+            let redeem_script = input.redeem_script.as_ref().expect("checked in output_type");
+            if redeem_script.to_p2sh().ok().as_ref() != Some(spk) {
+                return Err(FinalizeError::ScriptMismatch);
+            }
+        }
+        if matches!(output_type, Wsh | ShWsh) {
+            let witness_script = input.witness_script.as_ref().expect("checked in sighash_ecdsa");
+            let program = if output_type == ShWsh {
+                input.redeem_script.as_ref().expect("checked in output_type")
+            } else {
+                spk
+            };
+            if witness_script.to_p2wsh().ok().as_ref() != Some(program) {
+                return Err(FinalizeError::ScriptMismatch);
+            }
+        }
+        let is_valid = |pk: &PublicKey, sig: &ecdsa::Signature| {
+            sig.sighash_type == hash_ty
+                && secp.verify_ecdsa(&msg, &sig.signature, &pk.inner).is_ok()
+        };
+        let redeem_script_sig = || {
+            let redeem_script = input.redeem_script.as_ref().expect("checked in output_type");
+            let push = <&PushBytes>::try_from(redeem_script.as_bytes())
+                .expect("P2WPKH and P2WSH scripts are shorter than a push");
+            Builder::new().push_slice(push).into_script()
+        };
+
+        let (script_sig, witness) = match output_type {
+            Bare => {
+                let (pk, sig) = input
+                    .partial_sigs
+                    .iter()
+                    .find(|&(pk, sig)| {
+                        ScriptBuf::new_p2pkh(pk.pubkey_hash()) == *spk && is_valid(pk, sig)
+                    })
+                    .ok_or(FinalizeError::MissingSignature)?;
+                let script_sig =
+                    Builder::new().push_slice(sig.serialize()).push_key(*pk).into_script();
+                (script_sig, Witness::new())
+            }
+            Wpkh | ShWpkh => {
+                let wpkh: &Script = if output_type == ShWpkh {
+                    input.redeem_script.as_ref().expect("checked in output_type")
+                } else {
+                    spk
+                };
+                let (pk, sig) = input
+                    .partial_sigs
+                    .iter()
+                    .find(|&(pk, sig)| {
+                        let matches_script = match pk.wpubkey_hash() {
+                            Ok(hash) => ScriptBuf::new_p2wpkh(hash).as_script() == wpkh,
+                            Err(_) => false, // Uncompressed keys can't be used in segwit.
+                        };
+                        matches_script && is_valid(pk, sig)
+                    })
+                    .ok_or(FinalizeError::MissingSignature)?;
+                let script_sig =
+                    if output_type == ShWpkh { redeem_script_sig() } else { ScriptBuf::new() };
+                (script_sig, Witness::p2wpkh(*sig, pk.inner))
+            }
+            Wsh | ShWsh => {
+                let witness_script =
+                    input.witness_script.as_ref().expect("checked in sighash_ecdsa");
+                let (threshold, keys) =
+                    witness_script.parse_multisig().ok_or(FinalizeError::UnsupportedScript)?;
+                // The signatures must be in the same order as the keys in the script.
+                let sigs = keys
+                    .iter()
+                    .filter_map(|pk| input.partial_sigs.get(pk).filter(|&sig| is_valid(pk, sig)))
+                    .take(threshold)
+                    .collect::<Vec<_>>();
+                if sigs.len() < threshold {
+                    return Err(FinalizeError::NotEnoughSignatures {
+                        required: threshold,
+                        found: sigs.len(),
+                    });
+                }
+
+                let mut witness = Witness::new();
+                // `OP_CHECKMULTISIG` pops one more element than it uses.
+                witness.push_slice(&[]);
+                for sig in sigs {
+                    witness.push_ecdsa_signature(*sig);
+                }
+                witness.push(witness_script.as_bytes());
+                let script_sig =
+                    if output_type == ShWsh { redeem_script_sig() } else { ScriptBuf::new() };
+                (script_sig, witness)
+            }
+            Sh | Tr => unreachable!("rejected above"),
+        };
+
+        Ok(Input {
+            non_witness_utxo: input.non_witness_utxo.clone(),
+            witness_utxo: input.witness_utxo.clone(),
+            final_script_sig: Some(script_sig).filter(|script_sig| !script_sig.is_empty()),
+            final_script_witness: Some(witness).filter(|witness| !witness.is_empty()),
+            proprietary: input.proprietary.clone(),
+            unknown: input.unknown.clone(),
+            ..Default::default()
+        })
+    }
+
     /// Returns the sighash message to sign an ECDSA input along with the sighash type.
     ///
     /// Uses the [`EcdsaSighashType`] from this input if one is specified. If no sighash type is
@@ -1032,6 +1202,65 @@ impl From<sighash::TaprootError> for SignError {
     fn from(e: sighash::TaprootError) -> Self { SignError::TaprootError(e) }
 }
 
+/// Map of input index -> the error encountered while finalizing that input.
+pub type FinalizeErrors = BTreeMap<usize, FinalizeError>;
+
+/// Errors encountered while finalizing a PSBT input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FinalizeError {
+    /// Calculating the sighash of the input failed.
+    Sighash(SignError),
+    /// Finalizing inputs spending this kind of script is not supported.
+    UnsupportedScript,
+    /// The redeem or witness script does not hash to the script the spent output commits to.
+    ScriptMismatch,
+    /// There is no valid signature for the key the script commits to.
+    MissingSignature,
+    /// There are fewer valid signatures than the multisig threshold.
+    NotEnoughSignatures {
+        /// The threshold of the multisig script.
+        required: usize,
+        /// The number of valid signatures found.
+        found: usize,
+    },
+}
+
+internals::impl_from_infallible!(FinalizeError);
+
+impl fmt::Display for FinalizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FinalizeError::*;
+
+        match *self {
+            Sighash(ref e) => write_err!(f, "failed to calculate the sighash"; e),
+            UnsupportedScript => write!(f, "finalizing this kind of script is not supported"),
+            ScriptMismatch =>
+                write!(f, "the redeem or witness script does not match the spent output"),
+            MissingSignature => write!(f, "missing a valid signature"),
+            NotEnoughSignatures { required, found } =>
+                write!(f, "found {} valid signatures but {} are required", found, required),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FinalizeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use FinalizeError::*;
+
+        match *self {
+            Sighash(ref e) => Some(e),
+            UnsupportedScript | ScriptMismatch | MissingSignature | NotEnoughSignatures { .. } =>
+                None,
+        }
+    }
+}
+
+impl From<SignError> for FinalizeError {
+    fn from(e: SignError) -> Self { Self::Sighash(e) }
+}
+
 /// This error is returned when extracting a [`Transaction`] from a [`Psbt`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -1294,6 +1523,212 @@ fn trivial_psbt() {
         assert_eq!(psbt.serialize_hex(), "70736274ff01000a0200000000000000000000");
     }
 
+    #[test]
+    fn from_unsigned_tx_and_combine() {
+        let mut tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn { previous_output: OutPoint::NULL, ..Default::default() }],
+            output: vec![TxOut { value: Amount::ONE_BTC, script_pubkey: ScriptBuf::new() }],
+        };
+        tx.input[0].script_sig = ScriptBuf::from_bytes(vec![0x51]);
+        assert!(matches!(Psbt::from_unsigned_tx(tx.clone()), Err(Error::UnsignedTxHasScriptSigs)));
+        tx.input[0].script_sig = ScriptBuf::new();
+
+        let psbt = Psbt::from_unsigned_tx(tx.clone()).unwrap();
+        assert_eq!(psbt.inputs.len(), 1);
+        assert_eq!(psbt.outputs.len(), 1);
+        assert_eq!(Psbt::deserialize(&psbt.serialize()).unwrap(), psbt);
+
+        // Each party fills in a different field of the input.
+        let spent = TxOut { value: Amount::from_int_btc(2), script_pubkey: ScriptBuf::new() };
+        let mut updater = psbt.clone();
+        updater.inputs[0].witness_utxo = Some(spent.clone());
+        let sighash_type = PsbtSighashType::from(EcdsaSighashType::All);
+        let mut signer = psbt.clone();
+        signer.inputs[0].sighash_type = Some(sighash_type);
+
+        let mut combined = updater.clone();
+        combined.combine(signer).unwrap();
+        assert_eq!(combined.inputs[0].witness_utxo, Some(spent));
+        assert_eq!(combined.inputs[0].sighash_type, Some(sighash_type));
+        assert_eq!(Psbt::deserialize(&combined.serialize()).unwrap(), combined);
+
+        // PSBTs for different transactions cannot be combined.
+        tx.lock_time = absolute::LockTime::from_consensus(1);
+        let other = Psbt::from_unsigned_tx(tx).unwrap();
+        assert!(matches!(updater.combine(other), Err(Error::UnexpectedUnsignedTx { .. })));
+    }
+
+    #[test]
+    fn finalize_standard_inputs() {
+        use crate::address::script_pubkey::{BuilderExt as _, ScriptBufExt as _};
+        use crate::opcodes::all::OP_CHECKMULTISIG;
+
+        let secp = Secp256k1::new();
+        let keys = (1..=4)
+            .map(|i| PrivateKey::new(SecretKey::from_slice(&[i; 32]).unwrap(), NetworkKind::Test))
+            .collect::<Vec<_>>();
+        let pks = keys.iter().map(|sk| sk.public_key(&secp)).collect::<Vec<_>>();
+
+        let multisig = Builder::new()
+            .push_int(2)
+            .push_key(pks[1])
+            .push_key(pks[2])
+            .push_key(pks[3])
+            .push_int(3)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script();
+        let prev = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn::default()],
+            output: vec![
+                TxOut {
+                    value: Amount::from_sat(1000),
+                    script_pubkey: ScriptBuf::new_p2wpkh(pks[0].wpubkey_hash().unwrap()),
+                },
+                TxOut {
+                    value: Amount::from_sat(2000),
+                    script_pubkey: multisig.to_p2wsh().unwrap(),
+                },
+                TxOut {
+                    value: Amount::from_sat(3000),
+                    script_pubkey: ScriptBuf::new_p2pkh(pks[0].pubkey_hash()),
+                },
+            ],
+        };
+        let txid = prev.compute_txid();
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: (0..3)
+                .map(|vout| TxIn { previous_output: OutPoint { txid, vout }, ..Default::default() })
+                .collect(),
+            output: vec![TxOut { value: Amount::from_sat(5000), script_pubkey: ScriptBuf::new() }],
+        };
+
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        psbt.inputs[0].witness_utxo = Some(prev.output[0].clone());
+        psbt.inputs[1].witness_utxo = Some(prev.output[1].clone());
+        psbt.inputs[1].witness_script = Some(multisig.clone());
+        psbt.inputs[2].non_witness_utxo = Some(prev);
+
+        let sign = |psbt: &mut Psbt, input_index: usize, sk: &PrivateKey| {
+            let mut cache = SighashCache::new(&psbt.unsigned_tx);
+            let (msg, sighash_type) = psbt.sighash_ecdsa(input_index, &mut cache).unwrap();
+            let sig =
+                ecdsa::Signature { signature: secp.sign_ecdsa(&msg, &sk.inner), sighash_type };
+            psbt.inputs[input_index].partial_sigs.insert(sk.public_key(&secp), sig);
+        };
+
+        // Signatures made by the wrong key or for another input are not used.
+        sign(&mut psbt, 0, &keys[1]);
+        sign(&mut psbt, 1, &keys[3]);
+        let sig = psbt.inputs[0].partial_sigs[&pks[1]];
+        psbt.inputs[1].partial_sigs.insert(pks[1], sig);
+
+        let errors = psbt.clone().finalize(&secp).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[&0], FinalizeError::MissingSignature);
+        assert_eq!(errors[&1], FinalizeError::NotEnoughSignatures { required: 2, found: 1 });
+        assert_eq!(errors[&2], FinalizeError::MissingSignature);
+
+        sign(&mut psbt, 0, &keys[0]);
+        sign(&mut psbt, 1, &keys[1]);
+        sign(&mut psbt, 1, &keys[2]);
+        sign(&mut psbt, 2, &keys[0]);
+        psbt.finalize(&secp).unwrap();
+
+        let input = &psbt.inputs[0];
+        assert!(input.final_script_sig.is_none());
+        assert!(input.partial_sigs.is_empty());
+        let witness = input.final_script_witness.as_ref().unwrap();
+        assert_eq!(witness.len(), 2);
+        assert_eq!(witness.nth(1), Some(&pks[0].to_bytes()[..]));
+
+        let input = &psbt.inputs[1];
+        assert!(input.final_script_sig.is_none());
+        assert!(input.witness_script.is_none());
+        let witness = input.final_script_witness.as_ref().unwrap();
+        assert_eq!(witness.len(), 4);
+        assert!(witness.nth(0).unwrap().is_empty());
+        assert_eq!(witness.witness_script(), Some(multisig.as_script()));
+
+        let input = &psbt.inputs[2];
+        assert!(input.final_script_witness.is_none());
+        assert!(input.non_witness_utxo.is_some());
+        let script_sig = input.final_script_sig.as_ref().unwrap();
+        assert!(script_sig.as_bytes().ends_with(&pks[0].to_bytes()));
+
+        // Finalizing again is a no-op.
+        let finalized = psbt.clone();
+        psbt.finalize(&secp).unwrap();
+        assert_eq!(psbt, finalized);
+
+        let tx = psbt.extract_tx().unwrap();
+        assert_eq!(tx.input[1].witness.len(), 4);
+    }
+
+    #[test]
+    fn finalize_script_mismatch() {
+        use crate::address::script_pubkey::{BuilderExt as _, ScriptBufExt as _};
+        use crate::opcodes::all::OP_CHECKMULTISIG;
+
+        let secp = Secp256k1::new();
+        let keys = (1..=2)
+            .map(|i| PrivateKey::new(SecretKey::from_slice(&[i; 32]).unwrap(), NetworkKind::Test))
+            .collect::<Vec<_>>();
+        let pks = keys.iter().map(|sk| sk.public_key(&secp)).collect::<Vec<_>>();
+        let multisig = |pk: PublicKey| {
+            Builder::new()
+                .push_int(1)
+                .push_key(pk)
+                .push_int(1)
+                .push_opcode(OP_CHECKMULTISIG)
+                .into_script()
+        };
+        let p2wpkh = |pk: PublicKey| ScriptBuf::new_p2wpkh(pk.wpubkey_hash().unwrap());
+
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn::default(), TxIn::default()],
+            output: vec![TxOut { value: Amount::from_sat(1000), script_pubkey: ScriptBuf::new() }],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+
+        // The witness script is for the first key but the output commits to the second one.
+        psbt.inputs[0].witness_utxo = Some(TxOut {
+            value: Amount::from_sat(1000),
+            script_pubkey: multisig(pks[1]).to_p2wsh().unwrap(),
+        });
+        psbt.inputs[0].witness_script = Some(multisig(pks[0]));
+        // Likewise for the redeem script of a nested P2WPKH output.
+        psbt.inputs[1].witness_utxo = Some(TxOut {
+            value: Amount::from_sat(1000),
+            script_pubkey: p2wpkh(pks[1]).to_p2sh().unwrap(),
+        });
+        psbt.inputs[1].redeem_script = Some(p2wpkh(pks[0]));
+
+        // The signatures are valid for the scripts in the PSBT.
+        for input_index in 0..2 {
+            let mut cache = SighashCache::new(&psbt.unsigned_tx);
+            let (msg, sighash_type) = psbt.sighash_ecdsa(input_index, &mut cache).unwrap();
+            let sig =
+                ecdsa::Signature { signature: secp.sign_ecdsa(&msg, &keys[0].inner), sighash_type };
+            psbt.inputs[input_index].partial_sigs.insert(pks[0], sig);
+        }
+
+        let unfinalized = psbt.clone();
+        let errors = psbt.finalize(&secp).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[&0], FinalizeError::ScriptMismatch);
+        assert_eq!(errors[&1], FinalizeError::ScriptMismatch);
+        // Nothing is cleared from the inputs that failed.
+        assert_eq!(psbt, unfinalized);
+    }
+
     #[test]
     fn psbt_uncompressed_key() {
         let psbt: Psbt = hex_psbt("70736274ff01003302000000010000000000000000000000000000000000000000000000000000000000000000ffffffff00ffffffff000000000000420204bb0d5d0cca36e7b9c80f63bc04c1240babb83bcd2803ef7ac8b6e2af594291daec281e856c98d210c5ab14dfd5828761f8ee7d5f45ca21ad3e4c4b41b747a3a047304402204f67e2afb76142d44fae58a2495d33a3419daa26cd0db8d04f3452b63289ac0f022010762a9fb67e94cc5cad9026f6dc99ff7f070f4278d30fbc7d0c869dd38c7fe70100").unwrap();
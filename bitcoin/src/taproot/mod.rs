@@ -94,7 +94,7 @@ pub fn from_script(script: &Script, ver: LeafVersion) -> TapLeafHash {
         let mut eng = sha256t::Hash::<TapLeafTag>::engine();
         ver.to_consensus().consensus_encode(&mut eng).expect("engines don't error");
         script.consensus_encode(&mut eng).expect("engines don't error");
-        let inner = sha256t::Hash::<TapTweakTag>::from_engine(eng);
+        let inner = sha256t::Hash::<TapLeafTag>::from_engine(eng);
         TapLeafHash::from_byte_array(inner.to_byte_array())
     }
 }
@@ -1112,6 +1112,7 @@ impl ControlBlock {
     /// - [`TaprootError::InvalidTaprootLeafVersion`] if first byte of `sl` is not a valid leaf version.
     /// - [`TaprootError::InvalidInternalKey`] if internal key is invalid (first 32 bytes after the parity byte).
     /// - [`TaprootError::InvalidMerkleTreeDepth`] if Merkle tree is too deep (more than 128 levels).
+    #[doc(alias = "from_slice")]
     pub fn decode(sl: &[u8]) -> Result<ControlBlock, TaprootError> {
         if sl.len() < TAPROOT_CONTROL_BASE_SIZE
             || (sl.len() - TAPROOT_CONTROL_BASE_SIZE) % TAPROOT_CONTROL_NODE_SIZE != 0
@@ -1642,6 +1643,42 @@ fn test_vectors_core() {
         );
     }
 
+    #[test]
+    fn leaf_and_branch_hashes() {
+        // Leaf hash of the first script path spend in the BIP-341 wallet test vectors.
+        let script = ScriptBuf::from_hex(
+            "20d85a959b0290bf19bb89ed43c916be835475d013da4b362117393e25a48229b8ac",
+        )
+        .unwrap();
+        assert_eq!(
+            TapLeafHash::from_script(&script, LeafVersion::TapScript).to_string(),
+            "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"
+        );
+
+        // Expected values computed as SHA256(SHA256(tag) || SHA256(tag) || msg).
+        let leaf = |hex| {
+            let script = ScriptBuf::from_hex(hex).unwrap();
+            TapNodeHash::from_script(&script, LeafVersion::TapScript)
+        };
+        let (a, b) = (leaf("51"), leaf("52"));
+        assert_eq!(
+            a.to_string(),
+            "a85b2107f791b26a84e7586c28cec7cb61202ed3d01944d832500f363782d675"
+        );
+        assert_eq!(
+            b.to_string(),
+            "c276fef1386890619b80e10a4a328572d97493add269df1a15a7f89f8ae8ec09"
+        );
+
+        // The branch hash commits to the two child hashes in lexicographic order.
+        let branch = TapNodeHash::from_node_hashes(a, b);
+        assert_eq!(branch, TapNodeHash::from_node_hashes(b, a));
+        assert_eq!(
+            branch.to_string(),
+            "6496f0779f38b871013be71ee7dcce8fcdcc02afc4c688acb159fc5de2fba55e"
+        );
+    }
+
     fn _verify_tap_commitments(
         secp: &Secp256k1<VerifyOnly>,
         out_spk_hex: &str,
@@ -1709,6 +1746,53 @@ fn control_block_verify() {
         _verify_tap_commitments(&secp, "512093c7378d96518a75448821c4f7c8f4bae7ce60f804d03d1f0628dd5dd0f5de51", "04ffffffff203455139bf238a3067bd72ed77e0ab8db590330f55ed58dba7366b53bf4734279ba04feffffff87ab", "c1a0eb12e60a52614986c623cbb6621dcdba3a47e3be6b37e032b7a11c7b98f400c9a5cd1f6c8a81f5648e39f9810591df1c9a8f1fe97c92e03ecd7c0c016c951983e05473c6e8238cb4c780ea2ce62552b2a3eee068ceffc00517cd7b97e10dad");
     }
 
+    #[test]
+    fn control_block_from_witness() {
+        use crate::Witness;
+
+        let secp = Secp256k1::verification_only();
+        // One of the single leaf spends from `control_block_verify`.
+        let output_key = "567666e7df90e0450bb608e17c01ed3fbcfa5355a5f8273e34e583bfaa70ce09"
+            .parse::<XOnlyPublicKey>()
+            .unwrap();
+        let script = ScriptBuf::from_hex(
+            "203455139bf238a3067bd72ed77e0ab8db590330f55ed58dba7366b53bf4734279ac",
+        )
+        .unwrap();
+        let control = Vec::<u8>::from_hex(
+            "c1a0eb12e60a52614986c623cbb6621dcdba3a47e3be6b37e032b7a11c7b98f400",
+        )
+        .unwrap();
+        let witness = Witness::from_slice(&[&[0x01; 64][..], script.as_bytes(), &control[..]]);
+
+        let control_block = ControlBlock::decode(witness.taproot_control_block().unwrap()).unwrap();
+        assert_eq!(control_block.serialize(), control);
+        assert!(control_block.verify_taproot_commitment(
+            &secp,
+            output_key,
+            witness.tapscript().unwrap()
+        ));
+
+        // The commitment is to this exact script and output key.
+        assert!(!control_block.verify_taproot_commitment(&secp, output_key, Script::new()));
+        assert!(!control_block.verify_taproot_commitment(
+            &secp,
+            control_block.internal_key,
+            &script
+        ));
+
+        assert!(matches!(
+            ControlBlock::decode(&control[..32]),
+            Err(TaprootError::InvalidControlBlockSize(_))
+        ));
+        let mut annex = control.clone();
+        annex[0] = TAPROOT_ANNEX_PREFIX;
+        assert!(matches!(
+            ControlBlock::decode(&annex),
+            Err(TaprootError::InvalidTaprootLeafVersion(_))
+        ));
+    }
+
     #[test]
     fn build_huffman_tree() {
         let secp = Secp256k1::verification_only();
@@ -1840,6 +1924,39 @@ fn taptree_builder() {
         }
     }
 
+    #[test]
+    fn taptree_builder_single_leaf() {
+        let secp = Secp256k1::verification_only();
+        let internal_key = "93c7378d96518a75448821c4f7c8f4bae7ce60f804d03d1f0628dd5dd0f5de51"
+            .parse::<UntweakedPublicKey>()
+            .unwrap();
+        let script = ScriptBuf::from_hex("51").unwrap();
+
+        // A builder without leaves cannot be finalized.
+        assert!(TaprootBuilder::new().finalize(&secp, internal_key).is_err());
+
+        let tree_info = TaprootBuilder::new()
+            .add_leaf(0, script.clone())
+            .unwrap()
+            .finalize(&secp, internal_key)
+            .unwrap();
+
+        // With a single leaf the Merkle root is the leaf hash.
+        let root = TapNodeHash::from_script(&script, LeafVersion::TapScript);
+        assert_eq!(tree_info.merkle_root(), Some(root));
+        let (output_key, parity) = internal_key.tap_tweak(&secp, Some(root));
+        assert_eq!(tree_info.output_key(), output_key);
+        assert_eq!(tree_info.output_key_parity(), parity);
+
+        let ver_script = (script, LeafVersion::TapScript);
+        let ctrl_block = tree_info.control_block(&ver_script).unwrap();
+        assert_eq!(ctrl_block.size(), TAPROOT_CONTROL_BASE_SIZE);
+        assert!(ctrl_block.verify_taproot_commitment(&secp, output_key.to_inner(), &ver_script.0));
+
+        let other = (ScriptBuf::from_hex("52").unwrap(), LeafVersion::TapScript);
+        assert_eq!(tree_info.control_block(&other), None);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_leaf_version_serde() {
@@ -28,7 +28,9 @@
 /// ### Note on ordering
 ///
 /// Locktimes may be height- or time-based, and these metrics are incommensurate; there is no total
-/// ordering on locktimes. We therefore have implemented [`PartialOrd`] but not [`Ord`].
+/// ordering on locktimes. We therefore have implemented [`PartialOrd`] but not [`Ord`], comparing
+/// a height-based lock time with a time-based one returns `None` (and all of `<`, `>`, etc. are
+/// `false`).
 /// For `Transaction`, which has a locktime field, we implement a total ordering to make
 /// it easy to store transactions in sorted data structures, and use the locktime's 32-bit integer
 /// consensus encoding to order it. We also implement [`ordered::ArbitraryOrd`] if the "ordered"
@@ -454,6 +456,23 @@ fn parses_correctly_to_height_or_time() {
         assert!(lock.is_block_time());
     }
 
+    #[test]
+    fn height_or_time_at_threshold() {
+        // The genesis coinbase uses a zero lock time, which is a height.
+        assert!(LockTime::ZERO.is_block_height());
+        assert!(!LockTime::ZERO.is_block_time());
+
+        let last_height = LockTime::from_consensus(LOCK_TIME_THRESHOLD - 1);
+        assert!(last_height.is_block_height());
+        assert!(!last_height.is_block_time());
+
+        let first_time = LockTime::from_consensus(LOCK_TIME_THRESHOLD);
+        assert!(!first_time.is_block_height());
+        assert!(first_time.is_block_time());
+
+        assert!(LockTime::from_consensus(u32::MAX).is_block_time());
+    }
+
     #[test]
     fn satisfied_by_height() {
         let lock = LockTime::from_consensus(750_000);
@@ -525,4 +544,24 @@ fn incorrect_units_do_not_imply() {
         let lock = LockTime::from_consensus(750_005);
         assert!(!lock.is_implied_by(LockTime::from_consensus(1700000004)));
     }
+
+    #[test]
+    fn partial_ord_only_compares_same_unit() {
+        let low_height = LockTime::from_height(100).unwrap();
+        let high_height = LockTime::from_height(750_000).unwrap();
+        let low_time = LockTime::from_time(1_653_195_600).unwrap();
+        let high_time = LockTime::from_time(1_700_000_000).unwrap();
+
+        assert_eq!(low_height.partial_cmp(&high_height), Some(Ordering::Less));
+        assert_eq!(high_time.partial_cmp(&low_time), Some(Ordering::Greater));
+        assert_eq!(low_time.partial_cmp(&low_time), Some(Ordering::Equal));
+
+        assert_eq!(low_height.partial_cmp(&high_time), None);
+        assert_eq!(high_time.partial_cmp(&low_height), None);
+
+        // Lock times of a single unit can be sorted.
+        let mut heights = [high_height, LockTime::ZERO, low_height];
+        heights.sort_by(|a, b| a.partial_cmp(b).expect("same unit"));
+        assert_eq!(heights, [LockTime::ZERO, low_height, high_height]);
+    }
 }
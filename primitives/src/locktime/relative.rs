@@ -486,6 +486,29 @@ fn incorrect_units_do_not_imply() {
         assert!(!lock.is_implied_by(LockTime::from(height)));
     }
 
+    #[test]
+    fn sequence_satisfaction() {
+        let no_time = Time::from_512_second_intervals(0);
+
+        let lock = LockTime::from_sequence(Sequence::from_height(144)).unwrap();
+        assert_eq!(Sequence::from_height(144).to_relative_lock_time(), Some(lock));
+        assert!(!lock.is_satisfied_by(Height::from(143), no_time));
+        assert!(lock.is_satisfied_by(Height::from(144), no_time));
+        // A height lock is never satisfied by time passing.
+        assert!(!lock.is_satisfied_by(Height::from(0), Time::MAX));
+
+        let lock = LockTime::from_sequence(Sequence::from_512_second_intervals(10)).unwrap();
+        assert_eq!(Sequence::from_512_second_intervals(10).to_relative_lock_time(), Some(lock));
+        assert!(!lock.is_satisfied_by(Height::MAX, Time::from_512_second_intervals(9)));
+        assert!(lock.is_satisfied_by(Height::from(0), Time::from_512_second_intervals(10)));
+
+        // BIP 68 disable flag: the sequence carries no relative lock time.
+        for seq in [Sequence::MAX, Sequence::ENABLE_RBF_NO_LOCKTIME] {
+            assert!(LockTime::from_sequence(seq).is_err());
+            assert_eq!(seq.to_relative_lock_time(), None);
+        }
+    }
+
     #[test]
     fn consensus_round_trip() {
         assert!(LockTime::from_consensus(1 << 31).is_err());
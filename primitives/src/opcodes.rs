@@ -432,6 +432,81 @@ pub const fn decode_pushnum(self) -> Option<u8> {
             _ => None,
         }
     }
+
+    /// Returns true if this opcode pushes data or a number onto the stack.
+    ///
+    /// These are the opcodes up to and including `OP_PUSHNUM_16`, except for `OP_RESERVED`. Note
+    /// that Bitcoin Core's push-only check (used by `Script::is_push_only`) does accept
+    /// `OP_RESERVED`.
+    #[inline]
+    pub const fn is_push(self) -> bool {
+        self.code <= OP_PUSHNUM_16.code && self.code != OP_RESERVED.code
+    }
+
+    /// Returns true if this opcode was disabled in 2010 and fails any legacy script containing
+    /// it, executed or not.
+    ///
+    /// These are the splice, bitwise and arithmetic opcodes `OP_CAT`, `OP_SUBSTR`, `OP_LEFT`,
+    /// `OP_RIGHT`, `OP_INVERT`, `OP_AND`, `OP_OR`, `OP_XOR`, `OP_2MUL`, `OP_2DIV`, `OP_MUL`,
+    /// `OP_DIV`, `OP_MOD`, `OP_LSHIFT` and `OP_RSHIFT`. In tapscript their codes are `OP_SUCCESSx`
+    /// instead, see [`Opcode::classify`].
+    #[inline]
+    pub const fn is_disabled(self) -> bool {
+        matches!(self.code, 0x7e..=0x81 | 0x83..=0x86 | 0x8d | 0x8e | 0x95..=0x99)
+    }
+
+    /// Returns the kind of operation this opcode performs.
+    ///
+    /// This groups opcodes the way Bitcoin Core's `script.h` does and is meant for displaying or
+    /// linting scripts. Use [`Opcode::classify`] to find out how an opcode is executed.
+    #[inline]
+    pub const fn category(self) -> Category {
+        if self.is_disabled() {
+            return Category::Disabled;
+        }
+        match self.code {
+            0x00..=0x4f | 0x51..=0x60 => Category::Push,
+            // OP_NOP, OP_IF, OP_NOTIF, OP_ELSE, OP_ENDIF, OP_VERIFY and OP_RETURN.
+            0x61 | 0x63 | 0x64 | 0x67..=0x6a => Category::FlowControl,
+            // OP_TOALTSTACK to OP_TUCK, and OP_SIZE.
+            0x6b..=0x7d | 0x82 => Category::Stack,
+            // OP_EQUAL and OP_EQUALVERIFY.
+            0x87 | 0x88 => Category::Bitwise,
+            // OP_1ADD to OP_WITHIN.
+            0x8b..=0xa5 => Category::Arithmetic,
+            // OP_RIPEMD160 to OP_CHECKMULTISIGVERIFY, and OP_CHECKSIGADD.
+            0xa6..=0xaf | 0xba => Category::Crypto,
+            // OP_NOP1 to OP_NOP10, including OP_CLTV and OP_CSV.
+            0xb0..=0xb9 => Category::Expansion,
+            // OP_RESERVED, OP_VER, OP_VERIF, OP_VERNOTIF, OP_RESERVED1, OP_RESERVED2 and all
+            // unassigned codes.
+            _ => Category::Reserved,
+        }
+    }
+}
+
+/// The kind of operation an opcode performs, see [`Opcode::category`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[non_exhaustive]
+pub enum Category {
+    /// Pushes data or a number onto the stack.
+    Push,
+    /// Control flow, including `OP_VERIFY` and `OP_RETURN`.
+    FlowControl,
+    /// Stack manipulation.
+    Stack,
+    /// Bitwise logic, only `OP_EQUAL` and `OP_EQUALVERIFY` are enabled.
+    Bitwise,
+    /// Numeric operations.
+    Arithmetic,
+    /// Hashing and signature checking.
+    Crypto,
+    /// The `OP_NOPx` opcodes reserved for soft forks, including the lock time checks.
+    Expansion,
+    /// Disabled opcodes, see [`Opcode::is_disabled`].
+    Disabled,
+    /// Reserved and unassigned opcodes, these fail the script when executed.
+    Reserved,
 }
 
 impl From<u8> for Opcode {
@@ -601,6 +676,51 @@ fn decode_pushnum() {
         assert!(OP_NOP.decode_pushnum().is_none());
     }
 
+    #[test]
+    fn opcode_category() {
+        // The genesis output script is `<pubkey> OP_CHECKSIG`.
+        assert_eq!(OP_PUSHBYTES_65.category(), Category::Push);
+        assert_eq!(OP_CHECKSIG.category(), Category::Crypto);
+
+        let disabled = [
+            OP_CAT, OP_SUBSTR, OP_LEFT, OP_RIGHT, OP_INVERT, OP_AND, OP_OR, OP_XOR, OP_2MUL,
+            OP_2DIV, OP_MUL, OP_DIV, OP_MOD, OP_LSHIFT, OP_RSHIFT,
+        ];
+        for i in 0x00..=0xff_u8 {
+            let op = Opcode::from(i);
+            assert_eq!(op.is_disabled(), disabled.contains(&op), "{}", op);
+            assert_eq!(op.is_disabled(), op.category() == Category::Disabled, "{}", op);
+            assert_eq!(op.is_push(), op.category() == Category::Push, "{}", op);
+            // Disabled opcodes are illegal in legacy scripts.
+            if op.is_disabled() {
+                assert_eq!(op.classify(ClassifyContext::Legacy), Class::IllegalOp);
+            }
+        }
+
+        assert!(OP_PUSHBYTES_0.is_push());
+        assert!(OP_PUSHDATA4.is_push());
+        assert!(OP_PUSHNUM_NEG1.is_push());
+        assert!(OP_PUSHNUM_16.is_push());
+        assert!(!OP_RESERVED.is_push());
+        assert!(!OP_NOP.is_push());
+
+        assert_eq!(OP_IF.category(), Category::FlowControl);
+        assert_eq!(OP_RETURN.category(), Category::FlowControl);
+        assert_eq!(OP_DUP.category(), Category::Stack);
+        assert_eq!(OP_SIZE.category(), Category::Stack);
+        assert_eq!(OP_EQUALVERIFY.category(), Category::Bitwise);
+        assert_eq!(OP_ADD.category(), Category::Arithmetic);
+        assert_eq!(OP_WITHIN.category(), Category::Arithmetic);
+        assert_eq!(OP_CHECKMULTISIGVERIFY.category(), Category::Crypto);
+        assert_eq!(OP_CHECKSIGADD.category(), Category::Crypto);
+        assert_eq!(OP_CLTV.category(), Category::Expansion);
+        assert_eq!(OP_NOP10.category(), Category::Expansion);
+        assert_eq!(OP_VER.category(), Category::Reserved);
+        assert_eq!(OP_VERIF.category(), Category::Reserved);
+        assert_eq!(OP_RESERVED2.category(), Category::Reserved);
+        assert_eq!(OP_INVALIDOPCODE.category(), Category::Reserved);
+    }
+
     #[test]
     fn classify_test() {
         let op174 = OP_CHECKMULTISIG;
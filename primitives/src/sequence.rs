@@ -172,12 +172,16 @@ pub fn from_seconds_ceil(seconds: u32) -> Result<Self, TimeOverflowError> {
     }
 
     /// Creates a sequence from a u32 value.
+    ///
+    /// The value is used as is, exactly as it appears on the wire, no flags are interpreted.
     #[inline]
-    pub fn from_consensus(n: u32) -> Self { Sequence(n) }
+    pub const fn from_consensus(n: u32) -> Self { Sequence(n) }
 
     /// Returns the inner 32bit integer value of Sequence.
+    ///
+    /// This is the value that is consensus encoded (little-endian) in a transaction input.
     #[inline]
-    pub fn to_consensus_u32(self) -> u32 { self.0 }
+    pub const fn to_consensus_u32(self) -> u32 { self.0 }
 
     /// Creates a [`relative::LockTime`] from this [`Sequence`] number.
     #[inline]
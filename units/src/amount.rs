@@ -881,8 +881,23 @@ impl Amount {
     pub const SIZE: usize = 8; // Serialized length of a u64.
 
     /// Creates an [`Amount`] with satoshi precision and the given number of satoshis.
+    ///
+    /// This does not check the value against [`Amount::MAX_MONEY`], any `u64` is accepted. Use
+    /// [`Amount::checked_from_sat`] when the value comes from an untrusted source.
     pub const fn from_sat(satoshi: u64) -> Amount { Amount(satoshi) }
 
+    /// Creates an [`Amount`] from the given number of satoshis if it does not exceed
+    /// [`Amount::MAX_MONEY`].
+    ///
+    /// Returns [`None`] if `satoshi` is greater than `MAX_MONEY`.
+    pub const fn checked_from_sat(satoshi: u64) -> Option<Amount> {
+        if satoshi > Amount::MAX_MONEY.0 {
+            None
+        } else {
+            Some(Amount(satoshi))
+        }
+    }
+
     /// Gets the number of satoshis in this [`Amount`].
     pub fn to_sat(self) -> u64 { self.0 }
 
@@ -1088,8 +1103,11 @@ impl fmt::Debug for Amount {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{} SAT", self.to_sat()) }
 }
 
-// No one should depend on a binding contract for Display for this type.
-// Just using Bitcoin denominated string.
+/// Displays the amount in bitcoin with a `BTC` suffix, e.g. `0.5 BTC`.
+///
+/// Trailing zeros are trimmed, specify a precision (`{:.8}`) to show all decimal places, e.g.
+/// `0.50000000 BTC`. Either form round-trips through [`FromStr`]. Use [`Amount::display_in`] or
+/// [`Amount::display_dynamic`] for other denominations.
 impl fmt::Display for Amount {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self.display_in(Denomination::Bitcoin).show_denomination(), f)
@@ -1166,6 +1184,12 @@ impl TryFrom<SignedAmount> for Amount {
     fn try_from(value: SignedAmount) -> Result<Self, Self::Error> { value.to_unsigned() }
 }
 
+/// Sums the amounts.
+///
+/// The sum is not checked against [`Amount::MAX_MONEY`]. Overflowing a `u64` panics in debug
+/// builds and wraps in release builds, the same as summing the satoshi values does. Use
+/// [`CheckedSum::checked_sum`] to get `None` instead, e.g. when summing values from untrusted
+/// transactions.
 impl core::iter::Sum for Amount {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         let sats: u64 = iter.map(|amt| amt.0).sum();
@@ -1416,6 +1440,14 @@ pub fn is_negative(self) -> bool { self.0.is_negative() }
     /// Returns [`None`] if overflow occurred. (`self == MIN`)
     pub fn checked_abs(self) -> Option<SignedAmount> { self.0.checked_abs().map(SignedAmount) }
 
+    /// Returns `true` if this amount is within `-MAX_MONEY..=MAX_MONEY`.
+    ///
+    /// Intermediate values of fee and balance calculations may be negative but a value outside of
+    /// this range can not be the sum or difference of valid amounts.
+    pub fn is_within_max_money(self) -> bool {
+        -SignedAmount::MAX_MONEY.0 <= self.0 && self.0 <= SignedAmount::MAX_MONEY.0
+    }
+
     /// Checked addition.
     ///
     /// Returns [`None`] if overflow occurred.
@@ -1652,7 +1684,9 @@ pub mod serde {
     //!
     //! Since there is not a default way to serialize and deserialize Amounts, multiple
     //! ways are supported and it's up to the user to decide which serialiation to use.
-    //! The provided modules can be used as follows:
+    //! Without an attribute an [`Amount`] is serialized as an integer number of satoshis, the same
+    //! as [`as_sat`], which unlike [`as_btc`] does not go through a float and so never loses
+    //! precision. The provided modules can be used as follows:
     //!
     //! ```rust,ignore
     //! use serde::{Serialize, Deserialize};
@@ -2154,6 +2188,42 @@ fn checked_arithmetic() {
         assert_eq!(ssat(-6).checked_div(2), Some(ssat(-3)));
     }
 
+    #[test]
+    fn checked_from_sat_max_money() {
+        let genesis_subsidy = 50 * 100_000_000;
+        assert_eq!(Amount::checked_from_sat(genesis_subsidy), Some(Amount::from_int_btc(50)));
+        assert_eq!(Amount::checked_from_sat(0), Some(Amount::ZERO));
+        assert_eq!(Amount::checked_from_sat(Amount::MAX_MONEY.to_sat()), Some(Amount::MAX_MONEY));
+        assert_eq!(Amount::checked_from_sat(Amount::MAX_MONEY.to_sat() + 1), None);
+        assert_eq!(Amount::checked_from_sat(u64::MAX), None);
+
+        // `from_sat` accepts anything.
+        assert_eq!(Amount::from_sat(u64::MAX).to_sat(), u64::MAX);
+    }
+
+    #[test]
+    fn signed_amount_max_money_range() {
+        let max = SignedAmount::MAX_MONEY;
+
+        assert!(SignedAmount::ZERO.is_within_max_money());
+        assert!(max.is_within_max_money());
+        assert!((-max).is_within_max_money());
+        assert!(!(max + SignedAmount::ONE_SAT).is_within_max_money());
+        assert!(!(-max - SignedAmount::ONE_SAT).is_within_max_money());
+        assert!(!SignedAmount::MIN.is_within_max_money());
+
+        // A negative delta converts back once it has been checked non-negative.
+        let delta = SignedAmount::from_sat(-1_000);
+        assert!(delta.to_unsigned().is_err());
+        assert_eq!(
+            delta.checked_abs().map(SignedAmount::to_unsigned),
+            Some(Ok(Amount::from_sat(1_000)))
+        );
+        assert_eq!(SignedAmount::MIN.checked_abs(), None);
+        assert_eq!(Amount::MAX_MONEY.to_signed(), Ok(max));
+        assert_eq!(max.to_unsigned(), Ok(Amount::MAX_MONEY));
+    }
+
     #[test]
     #[cfg(not(debug_assertions))]
     fn unchecked_amount_add() {
@@ -2309,6 +2379,20 @@ fn parsing() {
         );
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn display_with_unit_suffix() {
+        let amount = Amount::from_sat(50_000_000);
+        assert_eq!(format!("{}", amount), "0.5 BTC");
+        assert_eq!(format!("{:.8}", amount), "0.50000000 BTC");
+        assert_eq!(format!("{:.8}", amount).parse::<Amount>(), Ok(amount));
+
+        assert_eq!(format!("{:.8}", Amount::ONE_SAT), "0.00000001 BTC");
+        assert_eq!(format!("{:.8}", Amount::MAX_MONEY), "21000000.00000000 BTC");
+        assert_eq!(format!("{:.8}", Amount::MAX_MONEY).parse::<Amount>(), Ok(Amount::MAX_MONEY));
+        assert_eq!(format!("{:.8}", SignedAmount::from_sat(-50_000_000)), "-0.50000000 BTC");
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     fn to_string() {
@@ -2675,6 +2759,26 @@ fn to_string_with_denomination_from_str_roundtrip() {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_default_is_sat() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct T {
+            pub amt: Amount,
+        }
+
+        serde_test::assert_tokens(
+            &T { amt: Amount::from_sat(123456789) },
+            &[
+                serde_test::Token::Struct { name: "T", len: 1 },
+                serde_test::Token::Str("amt"),
+                serde_test::Token::NewtypeStruct { name: "Amount" },
+                serde_test::Token::U64(123456789),
+                serde_test::Token::StructEnd,
+            ],
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn serde_as_sat() {
@@ -2837,6 +2941,24 @@ fn sum_amounts() {
         assert_eq!(SignedAmount::from_sat(1316), sum);
     }
 
+    #[test]
+    fn sum_amounts_past_max_money() {
+        let amounts = [Amount::MAX_MONEY, Amount::ONE_SAT];
+        let want = Amount::from_sat(Amount::MAX_MONEY.to_sat() + 1);
+        assert_eq!(amounts.into_iter().sum::<Amount>(), want);
+        assert_eq!(amounts.into_iter().checked_sum(), Some(want));
+
+        let amounts = [Amount::MAX, Amount::ONE_SAT];
+        assert_eq!(amounts.into_iter().checked_sum(), None);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn sum_amounts_overflow_wraps() {
+        let amounts = [Amount::MAX, Amount::ONE_SAT];
+        assert_eq!(amounts.into_iter().sum::<Amount>(), Amount::ZERO);
+    }
+
     #[test]
     fn checked_sum_amounts() {
         assert_eq!(Some(Amount::from_sat(0)), [].into_iter().checked_sum());
@@ -71,7 +71,18 @@ pub const fn to_sat_per_kwu(self) -> u64 { self.0 }
     pub const fn to_sat_per_vb_floor(self) -> u64 { self.0 / (1000 / 4) }
 
     /// Converts to sat/vB rounding up.
-    pub const fn to_sat_per_vb_ceil(self) -> u64 { (self.0 + (1000 / 4 - 1)) / (1000 / 4) }
+    ///
+    /// Rounding up guarantees that a fee computed from the result is never below the fee computed
+    /// from `self`, which is what Bitcoin Core expects when checking replacement fee increments.
+    pub const fn to_sat_per_vb_ceil(self) -> u64 {
+        let floor = self.to_sat_per_vb_floor();
+        // Avoids overflowing `self.0 + 249` for very large fee rates.
+        if self.0 % (1000 / 4) == 0 {
+            floor
+        } else {
+            floor + 1
+        }
+    }
 
     /// Checked multiplication.
     ///
@@ -199,6 +210,22 @@ fn raw_feerate_test() {
         assert_eq!(2, fee_rate.to_sat_per_vb_ceil());
     }
 
+    #[test]
+    fn sat_per_vb_rounding() {
+        assert_eq!(FeeRate(250).to_sat_per_vb_floor(), 1);
+        assert_eq!(FeeRate(250).to_sat_per_vb_ceil(), 1);
+        assert_eq!(FeeRate(251).to_sat_per_vb_floor(), 1);
+        assert_eq!(FeeRate(251).to_sat_per_vb_ceil(), 2);
+        assert_eq!(FeeRate(1).to_sat_per_vb_ceil(), 1);
+        assert_eq!(FeeRate::ZERO.to_sat_per_vb_ceil(), 0);
+        assert_eq!(FeeRate::MAX.to_sat_per_vb_ceil(), u64::MAX / 250 + 1);
+
+        // Converting from sat/vB and back is lossless.
+        let fee_rate = FeeRate::from_sat_per_vb(42).unwrap();
+        assert_eq!(fee_rate.to_sat_per_vb_floor(), 42);
+        assert_eq!(fee_rate.to_sat_per_vb_ceil(), 42);
+    }
+
     #[test]
     fn checked_mul_test() {
         let fee_rate = FeeRate(10).checked_mul(10).expect("expected feerate in sat/kwu");
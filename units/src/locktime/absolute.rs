@@ -8,6 +8,7 @@
 
 use internals::write_err;
 
+use crate::block::BlockInterval;
 #[cfg(feature = "alloc")]
 use crate::parse;
 use crate::parse::ParseIntError;
@@ -73,6 +74,35 @@ pub fn from_consensus(n: u32) -> Result<Height, ConversionError> {
     /// Converts this [`Height`] to its inner `u32` value.
     #[inline]
     pub fn to_consensus_u32(self) -> u32 { self.0 }
+
+    /// Returns the number of blocks from `other` up to `self`.
+    ///
+    /// Returns `None` if `other` is above `self`, for example when computing the depth of a
+    /// transaction whose block is no longer part of the chain ending at `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bitcoin_units::locktime::absolute::Height;
+    /// use bitcoin_units::BlockInterval;
+    ///
+    /// let tip = Height::from_consensus(800_010).unwrap();
+    /// let mined = Height::from_consensus(800_000).unwrap();
+    /// assert_eq!(tip.checked_sub(mined), Some(BlockInterval::from_u32(10)));
+    /// assert_eq!(mined.checked_sub(tip), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn checked_sub(self, other: Height) -> Option<BlockInterval> {
+        self.0.checked_sub(other.0).map(BlockInterval::from_u32)
+    }
+
+    /// Returns the number of blocks from `other` up to `self`, or zero if `other` is above `self`.
+    #[inline]
+    #[must_use]
+    pub fn saturating_sub(self, other: Height) -> BlockInterval {
+        BlockInterval::from_u32(self.0.saturating_sub(other.0))
+    }
 }
 
 impl fmt::Display for Height {
@@ -417,6 +447,39 @@ fn height_from_str_hex_invalid_hex_should_err() {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn height_from_consensus_boundaries() {
+        assert_eq!(Height::from_consensus(0), Ok(Height::MIN));
+        assert_eq!(Height::from_consensus(LOCK_TIME_THRESHOLD - 1), Ok(Height::MAX));
+        assert!(Height::from_consensus(LOCK_TIME_THRESHOLD).is_err());
+        assert!(Height::from_consensus(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn height_checked_sub() {
+        let tip = Height(800_010);
+        let mined = Height(800_000);
+
+        assert_eq!(tip.checked_sub(mined), Some(BlockInterval::from_u32(10)));
+        assert_eq!(tip.checked_sub(tip), Some(BlockInterval::ZERO));
+        assert_eq!(mined.checked_sub(tip), None);
+        assert_eq!(
+            Height::MAX.checked_sub(Height::MIN),
+            Some(BlockInterval::from_u32(499_999_999))
+        );
+
+        assert_eq!(tip.saturating_sub(mined), BlockInterval::from_u32(10));
+        assert_eq!(mined.saturating_sub(tip), BlockInterval::ZERO);
+    }
+
+    #[test]
+    fn time_from_consensus_boundaries() {
+        assert_eq!(Time::from_consensus(LOCK_TIME_THRESHOLD), Ok(Time::MIN));
+        assert_eq!(Time::from_consensus(u32::MAX), Ok(Time::MAX));
+        assert!(Time::from_consensus(LOCK_TIME_THRESHOLD - 1).is_err());
+        assert!(Time::from_consensus(0).is_err());
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     pub fn encode_decode_height() {
@@ -94,8 +94,15 @@ pub const fn to_kwu_floor(self) -> u64 { self.0 / 1000 }
     pub const fn to_vbytes_floor(self) -> u64 { self.0 / Self::WITNESS_SCALE_FACTOR }
 
     /// Converts to vB rounding up.
+    ///
+    /// This is how Bitcoin Core computes the virtual size of a transaction.
     pub const fn to_vbytes_ceil(self) -> u64 {
-        (self.0 + Self::WITNESS_SCALE_FACTOR - 1) / Self::WITNESS_SCALE_FACTOR
+        let vb = self.0 / Self::WITNESS_SCALE_FACTOR;
+        if self.0 % Self::WITNESS_SCALE_FACTOR == 0 {
+            vb
+        } else {
+            vb + 1
+        }
     }
 
     /// Checked addition.
@@ -119,7 +126,10 @@ pub fn checked_mul(self, rhs: u64) -> Option<Self> { self.0.checked_mul(rhs).map
     pub fn checked_div(self, rhs: u64) -> Option<Self> { self.0.checked_div(rhs).map(Self) }
 }
 
-/// Alternative will display the unit.
+/// Displays the weight as a plain number of weight units, e.g. `400000`.
+///
+/// The alternate form (`{:#}`) appends a lowercase unit, e.g. `400000 wu`. Only the plain form
+/// parses back with [`FromStr`](core::str::FromStr).
 impl fmt::Display for Weight {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if f.alternate() {
@@ -224,6 +234,19 @@ fn weight_constructor() {
         assert_eq!(Weight::ZERO, Weight::from_wu_usize(0_usize));
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn display_with_unit_suffix() {
+        use alloc::format;
+
+        assert_eq!(format!("{}", Weight::from_wu(400_000)), "400000");
+        assert_eq!(format!("{:#}", Weight::from_wu(400_000)), "400000 wu");
+        assert_eq!(format!("{:#}", Weight::MAX_BLOCK), "4000000 wu");
+
+        assert_eq!("400000".parse::<Weight>(), Ok(Weight::from_wu(400_000)));
+        assert!("400000 wu".parse::<Weight>().is_err());
+    }
+
     #[test]
     fn kilo_weight_constructor() {
         assert_eq!(Weight(1_000), Weight::from_kwu(1).expect("expected weight unit"));
@@ -289,6 +312,20 @@ fn to_vb_ceil() {
         assert_eq!(2, Weight(5).to_vbytes_ceil());
     }
 
+    #[test]
+    fn to_vbytes_ceil_no_overflow() {
+        assert_eq!(0, Weight(0).to_vbytes_ceil());
+        assert_eq!(1, Weight(1).to_vbytes_ceil());
+        assert_eq!(2, Weight(8).to_vbytes_ceil());
+        assert_eq!(0, Weight(3).to_vbytes_floor());
+        assert_eq!(1, Weight(7).to_vbytes_floor());
+
+        // Adding the rounding term first would overflow at the top of the range.
+        assert_eq!(u64::MAX / 4 + 1, Weight::MAX.to_vbytes_ceil());
+        assert_eq!(u64::MAX / 4, Weight::MAX.to_vbytes_floor());
+        assert_eq!(u64::MAX / 4, Weight(u64::MAX - 3).to_vbytes_ceil());
+    }
+
     #[test]
     fn checked_add() {
         let result = Weight(1).checked_add(Weight(1)).expect("expected weight unit");